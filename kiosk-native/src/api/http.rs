@@ -3,7 +3,10 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::config;
+use crate::config::Config;
+
+use super::image_cache::ImageCache;
+use super::websocket::PhotoInfo;
 
 #[derive(Error, Debug)]
 pub enum ApiError {
@@ -24,18 +27,22 @@ pub struct CreateSessionResponse {
 #[derive(Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
+    image_cache: ImageCache,
+    config: Config,
 }
 
 impl ApiClient {
-    pub fn new() -> Self {
+    pub fn new(config: Config) -> Self {
         Self {
             client: reqwest::Client::new(),
+            image_cache: ImageCache::new(),
+            config,
         }
     }
 
     /// Create a new photo session
     pub async fn create_session(&self) -> Result<CreateSessionResponse, ApiError> {
-        let url = config::sessions_url();
+        let url = self.config.sessions_url();
         log::info!("Creating session at {}", url);
 
         let response = self.client.post(&url).send().await?;
@@ -53,7 +60,7 @@ impl ApiClient {
 
     /// End an active session
     pub async fn end_session(&self, session_id: &str) -> Result<(), ApiError> {
-        let url = config::session_end_url(session_id);
+        let url = self.config.session_end_url(session_id);
         log::info!("Ending session {} at {}", session_id, url);
 
         let response = self.client.post(&url).send().await?;
@@ -68,10 +75,10 @@ impl ApiClient {
         Ok(())
     }
 
-    /// Trigger photo capture
-    /// Note: This just triggers the capture - actual photo events come via WebSocket
-    pub async fn capture(&self, session_id: &str) -> Result<(), ApiError> {
-        let url = config::capture_url(session_id);
+    /// Trigger a photo or boomerang-clip capture
+    /// Note: This just triggers the capture - actual photo/clip events come via WebSocket
+    pub async fn capture(&self, session_id: &str, mode: &str) -> Result<(), ApiError> {
+        let url = self.config.capture_url(session_id, mode);
         log::info!("Starting capture for session {} at {}", session_id, url);
 
         let response = self.client.post(&url).send().await?;
@@ -89,23 +96,101 @@ impl ApiClient {
         Ok(())
     }
 
-    /// Fetch image bytes from a URL
-    pub async fn fetch_image(&self, url: &str) -> Result<Vec<u8>, ApiError> {
-        log::debug!("Fetching image from {}", url);
-        let response = self.client.get(url).send().await?;
+    /// Delete a photo from a session
+    pub async fn delete_photo(&self, session_id: &str, photo_id: &str) -> Result<(), ApiError> {
+        let url = self.config.photo_delete_url(session_id, photo_id);
+        log::info!("Deleting photo {} from session {}", photo_id, session_id);
+
+        let response = self.client.delete(&url).send().await?;
 
         if !response.status().is_success() {
             let status = response.status();
-            return Err(ApiError::Server(format!("Failed to fetch image: {}", status)));
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError::Server(format!("{}: {}", status, body)));
         }
 
-        let bytes = response.bytes().await?;
-        Ok(bytes.to_vec())
+        Ok(())
+    }
+
+    /// Mark a photo for printing
+    pub async fn mark_photo_for_print(
+        &self,
+        session_id: &str,
+        photo_id: &str,
+    ) -> Result<(), ApiError> {
+        let url = self.config.photo_print_url(session_id, photo_id);
+        log::info!(
+            "Marking photo {} for print in session {}",
+            photo_id,
+            session_id
+        );
+
+        let response = self.client.post(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError::Server(format!("{}: {}", status, body)));
+        }
+
+        Ok(())
+    }
+
+    /// Upload a re-encoded photo from the photo editor, replacing the
+    /// original on the server, and return the updated photo record
+    pub async fn upload_edited_photo(
+        &self,
+        session_id: &str,
+        photo_id: &str,
+        bytes: Vec<u8>,
+    ) -> Result<PhotoInfo, ApiError> {
+        let url = self.config.photo_edit_url(session_id, photo_id);
+        log::info!("Uploading edited photo {} for session {}", photo_id, session_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "image/jpeg")
+            .body(bytes)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError::Server(format!("{}: {}", status, body)));
+        }
+
+        let photo: PhotoInfo = response.json().await?;
+        Ok(photo)
+    }
+
+    /// Fetch image bytes from a URL, serving from the memory/disk cache (and
+    /// joining an in-flight request for the same URL) before hitting the
+    /// network
+    pub async fn fetch_image(&self, url: &str) -> Result<Vec<u8>, ApiError> {
+        let client = self.client.clone();
+        let fetch_url = url.to_string();
+
+        self.image_cache
+            .dedup_fetch(url, move || async move {
+                log::debug!("Fetching image from {}", fetch_url);
+                let response = client.get(&fetch_url).send().await?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(ApiError::Server(format!("Failed to fetch image: {}", status)));
+                }
+
+                let bytes = response.bytes().await?;
+                Ok(bytes.to_vec())
+            })
+            .await
     }
-}
 
-impl Default for ApiClient {
-    fn default() -> Self {
-        Self::new()
+    /// Drop every cached thumbnail/photo, both the in-memory LRU and the
+    /// on-disk tier
+    pub fn clear_image_cache(&self) {
+        self.image_cache.clear();
     }
 }
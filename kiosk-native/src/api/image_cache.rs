@@ -0,0 +1,221 @@
+//! Two-tier (memory + disk) cache for downloaded image bytes, with
+//! in-flight request deduplication, used by `ApiClient::fetch_image` so
+//! replaying the same photo strip doesn't re-download the same JPEGs.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use crate::config;
+
+use super::http::ApiError;
+
+/// Bounded in-memory LRU of downloaded image bytes, evicted by total byte
+/// size rather than entry count since photo and thumbnail sizes vary widely.
+struct MemoryCache {
+    entries: VecDeque<(String, Vec<u8>)>,
+    total_bytes: usize,
+    budget_bytes: usize,
+}
+
+impl MemoryCache {
+    fn new(budget_bytes: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            total_bytes: 0,
+            budget_bytes,
+        }
+    }
+
+    fn get(&mut self, url: &str) -> Option<Vec<u8>> {
+        let pos = self
+            .entries
+            .iter()
+            .position(|(cached_url, _)| cached_url == url)?;
+        let (_, bytes) = self.entries.remove(pos).expect("position just checked");
+        self.entries.push_front((url.to_string(), bytes.clone()));
+        Some(bytes)
+    }
+
+    fn insert(&mut self, url: String, bytes: Vec<u8>) {
+        if let Some(pos) = self
+            .entries
+            .iter()
+            .position(|(cached_url, _)| cached_url == &url)
+        {
+            let (_, old) = self.entries.remove(pos).expect("position just checked");
+            self.total_bytes -= old.len();
+        }
+
+        self.total_bytes += bytes.len();
+        self.entries.push_front((url, bytes));
+
+        while self.total_bytes > self.budget_bytes {
+            match self.entries.pop_back() {
+                Some((_, evicted)) => self.total_bytes -= evicted.len(),
+                None => break,
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+    }
+}
+
+/// Two-tier cache (in-memory LRU + on-disk directory) of downloaded image
+/// bytes, keyed by URL, with in-flight request deduplication so concurrent
+/// fetches of the same URL share a single HTTP request.
+#[derive(Clone)]
+pub struct ImageCache {
+    memory: Arc<Mutex<MemoryCache>>,
+    disk_dir: PathBuf,
+    in_flight: Arc<Mutex<HashMap<String, broadcast::Sender<Result<Vec<u8>, String>>>>>,
+}
+
+impl ImageCache {
+    pub fn new() -> Self {
+        let disk_dir = PathBuf::from(config::IMAGE_CACHE_DIR);
+        if let Err(e) = std::fs::create_dir_all(&disk_dir) {
+            log::warn!(
+                "Failed to create image cache directory {:?}: {}",
+                disk_dir,
+                e
+            );
+        }
+
+        Self {
+            memory: Arc::new(Mutex::new(MemoryCache::new(
+                config::IMAGE_CACHE_MEMORY_BUDGET_BYTES,
+            ))),
+            disk_dir,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Look up `url` in memory, then on disk (promoting a disk hit back into
+    /// memory), or return `None` if neither tier has it.
+    fn get(&self, url: &str) -> Option<Vec<u8>> {
+        if let Some(bytes) = self
+            .memory
+            .lock()
+            .expect("image memory cache poisoned")
+            .get(url)
+        {
+            return Some(bytes);
+        }
+
+        let bytes = std::fs::read(self.disk_path(url)).ok()?;
+        self.memory
+            .lock()
+            .expect("image memory cache poisoned")
+            .insert(url.to_string(), bytes.clone());
+        Some(bytes)
+    }
+
+    /// Write `bytes` into both cache tiers.
+    fn store(&self, url: &str, bytes: &[u8]) {
+        self.memory
+            .lock()
+            .expect("image memory cache poisoned")
+            .insert(url.to_string(), bytes.to_vec());
+
+        if let Err(e) = std::fs::write(self.disk_path(url), bytes) {
+            log::warn!("Failed to write image cache entry for {}: {}", url, e);
+        }
+    }
+
+    /// Drop every cached entry, both tiers.
+    pub fn clear(&self) {
+        self.memory
+            .lock()
+            .expect("image memory cache poisoned")
+            .clear();
+
+        if let Err(e) = std::fs::remove_dir_all(&self.disk_dir) {
+            log::warn!("Failed to clear on-disk image cache: {}", e);
+        }
+        if let Err(e) = std::fs::create_dir_all(&self.disk_dir) {
+            log::warn!(
+                "Failed to recreate image cache directory {:?}: {}",
+                self.disk_dir,
+                e
+            );
+        }
+    }
+
+    fn disk_path(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.disk_dir.join(format!("{:016x}.bin", hasher.finish()))
+    }
+
+    /// Serve `url` from cache if present; otherwise run `fetch` - but if
+    /// another call is already fetching the same URL, await its result
+    /// instead of issuing a second HTTP request.
+    pub async fn dedup_fetch<F, Fut>(&self, url: &str, fetch: F) -> Result<Vec<u8>, ApiError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<u8>, ApiError>>,
+    {
+        if let Some(bytes) = self.get(url) {
+            return Ok(bytes);
+        }
+
+        let mut joined = None;
+        {
+            let mut in_flight = self.in_flight.lock().expect("in-flight cache poisoned");
+            if let Some(sender) = in_flight.get(url) {
+                joined = Some(sender.subscribe());
+            } else {
+                let (sender, _) = broadcast::channel(1);
+                in_flight.insert(url.to_string(), sender);
+            }
+        }
+
+        if let Some(mut receiver) = joined {
+            return match receiver.recv().await {
+                Ok(Ok(bytes)) => Ok(bytes),
+                Ok(Err(e)) => Err(ApiError::Server(e)),
+                Err(_) => Err(ApiError::Server(
+                    "in-flight image fetch was dropped".to_string(),
+                )),
+            };
+        }
+
+        // We're the leader for this URL - fetch it and notify anyone who
+        // joined us while we were in flight, then clean up our entry.
+        let result = fetch().await;
+
+        if let Some(sender) = self
+            .in_flight
+            .lock()
+            .expect("in-flight cache poisoned")
+            .remove(url)
+        {
+            let broadcast_result = match &result {
+                Ok(bytes) => Ok(bytes.clone()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = sender.send(broadcast_result);
+        }
+
+        if let Ok(bytes) = &result {
+            self.store(url, bytes);
+        }
+
+        result
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,8 @@
 //! API clients for PicPop backend communication.
 
 pub mod http;
+pub mod image_cache;
 pub mod websocket;
 
 pub use http::ApiClient;
-pub use websocket::{WsEvent, WsHandle, PhotoInfo};
+pub use websocket::{MediaKind, PhotoInfo, WsEvent, WsHandle};
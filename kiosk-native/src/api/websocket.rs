@@ -1,10 +1,22 @@
 //! WebSocket client for real-time kiosk events.
 
-use futures_util::{SinkExt, StreamExt};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use rustls::{ClientConfig as TlsClientConfig, RootCertStore};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::mpsc as tokio_mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio::sync::oneshot;
+use tokio_tungstenite::tungstenite::error::ProtocolError;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::{
+    connect_async, connect_async_tls_with_config, tungstenite::Message, Connector,
+};
 
 use crate::config;
 
@@ -16,6 +28,12 @@ pub enum WsError {
     Url(#[from] url::ParseError),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("Request timed out waiting for a response")]
+    Timeout,
+    #[error("WebSocket connection closed before a response arrived")]
+    Closed,
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
 }
 
 /// Events received from the WebSocket
@@ -37,8 +55,22 @@ pub enum WsEvent {
     SessionEnded,
     /// Connection established
     Connected,
-    /// Connection lost (will attempt reconnect)
-    Disconnected,
+    /// Connection lost; `attempt` is the 1-indexed reconnect attempt about to run
+    Disconnected { attempt: u32 },
+    /// Remote SDP answer for a WebRTC preview offer we sent
+    SdpAnswer(String),
+    /// Remote ICE candidate for the WebRTC preview transport
+    IceCandidate { candidate: String, sdp_mline_index: u32 },
+}
+
+/// Distinguishes a still photo from a short looping "boomerang" clip, so the
+/// UI knows whether to render a static image or a muted, looping video
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    #[default]
+    Image,
+    Clip,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,19 +80,42 @@ pub struct PhotoInfo {
     pub thumbnail_url: String,
     #[serde(rename = "webUrl")]
     pub web_url: String,
+    #[serde(default)]
+    pub kind: MediaKind,
 }
 
 #[derive(Debug, Deserialize)]
 struct WsMessage {
+    /// Present only on responses to a `WsHandle::request` call, echoing the
+    /// id it was sent with
+    #[serde(default)]
+    id: Option<u64>,
     #[serde(rename = "type")]
     msg_type: String,
     #[serde(default)]
     data: Option<serde_json::Value>,
 }
 
+/// An item on the spawned task's internal outbound queue
+enum Outbound {
+    /// A fire-and-forget frame, e.g. an SDP offer or ICE candidate
+    Message(Message),
+    /// A request awaiting a response carrying a matching `id`
+    Request {
+        id: u64,
+        message: Message,
+        respond_to: oneshot::Sender<serde_json::Value>,
+    },
+    /// A request that timed out client-side; drop its pending entry so it
+    /// doesn't linger in the map forever if a response never arrives
+    CancelRequest(u64),
+}
+
 /// Handle for controlling the WebSocket connection
 pub struct WsHandle {
     shutdown_tx: tokio_mpsc::Sender<()>,
+    outbound_tx: tokio_mpsc::UnboundedSender<Outbound>,
+    next_request_id: Arc<AtomicU64>,
 }
 
 impl WsHandle {
@@ -68,54 +123,237 @@ impl WsHandle {
     pub async fn close(&self) {
         let _ = self.shutdown_tx.send(()).await;
     }
+
+    /// Send a local WebRTC SDP offer to the remote peer (piggybacks on this
+    /// session's WebSocket rather than opening a dedicated signalling channel)
+    pub fn send_sdp_offer(&self, sdp: &str) {
+        self.send_json(serde_json::json!({
+            "type": "sdp_offer",
+            "data": { "sdp": sdp },
+        }));
+    }
+
+    /// Send a locally-gathered WebRTC ICE candidate to the remote peer
+    pub fn send_ice_candidate(&self, candidate: &str, sdp_mline_index: u32) {
+        self.send_json(serde_json::json!({
+            "type": "ice_candidate",
+            "data": { "candidate": candidate, "sdpMLineIndex": sdp_mline_index },
+        }));
+    }
+
+    /// Send a request (e.g. start capture, cancel countdown, end session) and
+    /// await the response carrying the same `id`, modeled on ethers-providers'
+    /// ws transport. Times out after `config::WS_REQUEST_TIMEOUT_MS`, and
+    /// resolves to `WsError::Closed` if the connection drops while pending.
+    pub async fn request(
+        &self,
+        msg_type: &str,
+        data: serde_json::Value,
+    ) -> Result<serde_json::Value, WsError> {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let payload = serde_json::json!({ "id": id, "type": msg_type, "data": data });
+        let (respond_to, rx) = oneshot::channel();
+
+        self.outbound_tx
+            .send(Outbound::Request {
+                id,
+                message: Message::Text(payload.to_string()),
+                respond_to,
+            })
+            .map_err(|_| WsError::Closed)?;
+
+        match tokio::time::timeout(
+            std::time::Duration::from_millis(config::WS_REQUEST_TIMEOUT_MS),
+            rx,
+        )
+        .await
+        {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) => Err(WsError::Closed),
+            Err(_) => {
+                let _ = self.outbound_tx.send(Outbound::CancelRequest(id));
+                Err(WsError::Timeout)
+            }
+        }
+    }
+
+    fn send_json(&self, value: serde_json::Value) {
+        let _ = self
+            .outbound_tx
+            .send(Outbound::Message(Message::Text(value.to_string())));
+    }
 }
 
 /// Callback type for WebSocket events
 pub type WsCallback = Box<dyn Fn(WsEvent) + Send + Sync>;
 
-/// Connect to the WebSocket and spawn a task to handle messages
-/// Uses a callback to send events to the main thread
-pub fn connect<F>(
+/// Custom TLS trust configuration for `wss://` endpoints, for kiosks whose
+/// backend presents a self-signed or privately-issued certificate that isn't
+/// in the system trust store. Supplying one opts the connection out of the
+/// system trust store entirely - only certificates chaining to this bundle
+/// are trusted.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    ca_certificate: Vec<u8>,
+}
+
+impl TlsConfig {
+    /// Build a `TlsConfig` from PEM-encoded CA certificate bytes
+    pub fn from_ca_pem(ca_certificate: Vec<u8>) -> Self {
+        Self { ca_certificate }
+    }
+
+    /// Build a `TlsConfig` by reading a PEM-encoded CA bundle from disk
+    pub fn from_ca_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            ca_certificate: std::fs::read(path)?,
+        })
+    }
+
+    /// Build the `tokio_tungstenite::Connector` this config describes,
+    /// following the `create_client_config` pattern used by the deno_websocket
+    /// transport: a `rustls::RootCertStore` seeded only with the supplied CA
+    /// bundle (no system roots), wired into a `rustls::ClientConfig` that
+    /// requires no client certificate.
+    fn build_connector(&self) -> Result<Connector, WsError> {
+        let mut root_store = RootCertStore::empty();
+        let mut reader = std::io::BufReader::new(self.ca_certificate.as_slice());
+        for cert in rustls_pemfile::certs(&mut reader) {
+            let cert = cert.map_err(|e| WsError::Tls(e.to_string()))?;
+            root_store
+                .add(cert)
+                .map_err(|e| WsError::Tls(e.to_string()))?;
+        }
+
+        if root_store.is_empty() {
+            return Err(WsError::Tls("no CA certificates found in bundle".into()));
+        }
+
+        let client_config = TlsClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+
+        Ok(Connector::Rustls(Arc::new(client_config)))
+    }
+}
+
+/// Reconnect backoff policy, mirroring `VideoPipeline`'s `RetryPolicy`
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    /// Base delay before the first reconnect attempt (in milliseconds)
+    pub base_delay_ms: u64,
+    /// Ceiling the exponential backoff delay is capped at (in milliseconds)
+    pub max_delay_ms: u64,
+    /// Reconnect attempts to give up after, or `None` to retry forever
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: config::ws_reconnect_base_delay_ms(),
+            max_delay_ms: config::ws_reconnect_max_delay_ms(),
+            max_attempts: config::WS_RECONNECT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+/// Connect to the WebSocket and spawn a task to handle messages, using the
+/// default reconnect policy. Uses a callback to send events to the main thread.
+/// Pass `tls` to pin a custom CA bundle for a `wss://` endpoint; `None` uses
+/// the system trust store.
+pub fn connect<F>(session_id: String, tls: Option<TlsConfig>, callback: F) -> WsHandle
+where
+    F: Fn(WsEvent) + Send + Sync + 'static,
+{
+    connect_with_policy(session_id, tls, ReconnectPolicy::default(), callback)
+}
+
+/// Connect to the WebSocket with a custom reconnect policy and spawn a task
+/// to handle messages. Uses a callback to send events to the main thread
+pub fn connect_with_policy<F>(
     session_id: String,
+    tls: Option<TlsConfig>,
+    policy: ReconnectPolicy,
     callback: F,
 ) -> WsHandle
 where
     F: Fn(WsEvent) + Send + Sync + 'static,
 {
     let (shutdown_tx, mut shutdown_rx) = tokio_mpsc::channel::<()>(1);
+    let (outbound_tx, mut outbound_rx) = tokio_mpsc::unbounded_channel::<Outbound>();
     let callback = std::sync::Arc::new(callback);
+    let next_request_id = Arc::new(AtomicU64::new(0));
 
     tokio::spawn(async move {
+        let mut attempt: u32 = 0;
+        let mut pending: BTreeMap<u64, oneshot::Sender<serde_json::Value>> = BTreeMap::new();
+
         loop {
             let url = config::ws_url(&session_id);
             log::info!("Connecting to WebSocket: {}", url);
 
-            match connect_async(&url).await {
+            let connect_result = match &tls {
+                Some(tls_config) => match tls_config.build_connector() {
+                    Ok(connector) => {
+                        connect_async_tls_with_config(&url, None, false, Some(connector))
+                            .await
+                            .map_err(WsError::from)
+                    }
+                    Err(e) => Err(e),
+                },
+                None => connect_async(&url).await.map_err(WsError::from),
+            };
+
+            match connect_result {
                 Ok((ws_stream, _)) => {
                     log::info!("WebSocket connected");
                     callback(WsEvent::Connected);
+                    attempt = 0;
 
                     let (mut write, mut read) = ws_stream.split();
+                    let mut last_activity = std::time::Instant::now();
+                    let mut heartbeat = tokio::time::interval(std::time::Duration::from_millis(
+                        config::ws_heartbeat_interval_ms(),
+                    ));
+                    heartbeat.tick().await; // first tick fires immediately; skip it
 
                     loop {
                         tokio::select! {
                             _ = shutdown_rx.recv() => {
                                 log::info!("WebSocket shutdown requested");
-                                let _ = write.close().await;
+                                graceful_close(&mut write, &mut read).await;
                                 return;
                             }
                             msg = read.next() => {
                                 match msg {
                                     Some(Ok(Message::Text(text))) => {
-                                        if let Some(event) = parse_message(&text) {
-                                            callback(event);
+                                        last_activity = std::time::Instant::now();
+                                        if !complete_pending_request(&text, &mut pending) {
+                                            if let Some(event) = parse_message(&text) {
+                                                callback(event);
+                                            }
                                         }
                                     }
                                     Some(Ok(Message::Ping(data))) => {
+                                        last_activity = std::time::Instant::now();
                                         let _ = write.send(Message::Pong(data)).await;
                                     }
-                                    Some(Ok(Message::Close(_))) => {
-                                        log::info!("WebSocket closed by server");
+                                    Some(Ok(Message::Pong(_))) => {
+                                        last_activity = std::time::Instant::now();
+                                    }
+                                    Some(Ok(Message::Close(frame))) => {
+                                        log::info!("WebSocket closed by server: {:?}", frame);
+                                        // Reply with the same close frame to complete the
+                                        // handshake before tearing the connection down
+                                        if let Err(e) = write.send(Message::Close(frame)).await {
+                                            if !is_send_after_closing(&e) {
+                                                log::warn!(
+                                                    "Failed to acknowledge WebSocket close: {}",
+                                                    e
+                                                );
+                                            }
+                                        }
                                         break;
                                     }
                                     Some(Err(e)) => {
@@ -129,6 +367,39 @@ where
                                     _ => {}
                                 }
                             }
+                            Some(out) = outbound_rx.recv() => {
+                                match out {
+                                    Outbound::Message(message) => {
+                                        if let Err(e) = write.send(message).await {
+                                            log::error!("Failed to send WebSocket message: {}", e);
+                                        }
+                                    }
+                                    Outbound::Request { id, message, respond_to } => {
+                                        pending.insert(id, respond_to);
+                                        if let Err(e) = write.send(message).await {
+                                            log::error!("Failed to send WebSocket request: {}", e);
+                                            pending.remove(&id);
+                                        }
+                                    }
+                                    Outbound::CancelRequest(id) => {
+                                        pending.remove(&id);
+                                    }
+                                }
+                            }
+                            _ = heartbeat.tick() => {
+                                let idle = last_activity.elapsed();
+                                if idle > std::time::Duration::from_millis(config::ws_liveness_timeout_ms()) {
+                                    log::warn!(
+                                        "No WebSocket activity for {:?}, treating connection as dead",
+                                        idle
+                                    );
+                                    break;
+                                }
+                                if let Err(e) = write.send(Message::Ping(Vec::new())).await {
+                                    log::error!("Failed to send WebSocket heartbeat ping: {}", e);
+                                    break;
+                                }
+                            }
                         }
                     }
                 }
@@ -137,7 +408,32 @@ where
                 }
             }
 
-            callback(WsEvent::Disconnected);
+            // Dropping the pending senders completes their receivers with an
+            // error, so in-flight `WsHandle::request` callers don't hang
+            // across a reconnect
+            pending.clear();
+
+            callback(WsEvent::Disconnected {
+                attempt: attempt + 1,
+            });
+
+            if let Some(max_attempts) = policy.max_attempts {
+                if attempt >= max_attempts {
+                    log::error!(
+                        "Giving up on WebSocket reconnection after {} attempts",
+                        max_attempts
+                    );
+                    return;
+                }
+            }
+
+            let delay = backoff_delay_ms(&policy, attempt);
+            attempt += 1;
+            log::info!(
+                "Reconnecting to WebSocket in {}ms (attempt {})",
+                delay,
+                attempt
+            );
 
             // Wait before reconnecting, but check for shutdown
             tokio::select! {
@@ -145,14 +441,147 @@ where
                     log::info!("WebSocket shutdown during reconnect wait");
                     return;
                 }
-                _ = tokio::time::sleep(tokio::time::Duration::from_millis(
-                    config::WS_RECONNECT_DELAY_MS
-                )) => {}
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(delay)) => {}
             }
         }
     });
 
-    WsHandle { shutdown_tx }
+    WsHandle {
+        shutdown_tx,
+        outbound_tx,
+        next_request_id,
+    }
+}
+
+/// Connect to the WebSocket and expose events as a `Stream` rather than via a
+/// callback, using the default reconnect policy - for callers that want to
+/// `select!` over the event source or otherwise compose it with other async
+/// streams instead of registering a `Send + Sync` `Fn`, matching the pubsub
+/// ergonomics of ethers-providers
+pub fn connect_stream(
+    session_id: String,
+    tls: Option<TlsConfig>,
+) -> (WsHandle, impl Stream<Item = WsEvent>) {
+    connect_stream_with_policy(session_id, tls, ReconnectPolicy::default())
+}
+
+/// Connect to the WebSocket with a custom reconnect policy and expose events
+/// as a `Stream` rather than via a callback; see `connect_stream`.
+pub fn connect_stream_with_policy(
+    session_id: String,
+    tls: Option<TlsConfig>,
+    policy: ReconnectPolicy,
+) -> (WsHandle, impl Stream<Item = WsEvent>) {
+    let (tx, rx) = tokio::sync::broadcast::channel(32);
+    let handle = connect_with_policy(session_id, tls, policy, move |event| {
+        // An error here just means every receiver has been dropped
+        let _ = tx.send(event);
+    });
+    (handle, broadcast_into_stream(rx))
+}
+
+/// Adapt a `broadcast::Receiver` into a plain `Stream`, skipping lag gaps
+/// (logging how many events were dropped) rather than ending the stream
+fn broadcast_into_stream(
+    rx: tokio::sync::broadcast::Receiver<WsEvent>,
+) -> impl Stream<Item = WsEvent> {
+    futures_util::stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((event, rx)),
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("WebSocket event stream lagged, dropped {} events", skipped);
+                }
+            }
+        }
+    })
+}
+
+/// Perform a graceful WebSocket close handshake: send an explicit close frame,
+/// wait a short bounded time for the server's acknowledgement, then close the
+/// underlying connection. `SendAfterClosing` is expected when the peer already
+/// initiated the close, so it's logged rather than surfaced as a hard error.
+async fn graceful_close<W, R>(write: &mut W, read: &mut R)
+where
+    W: futures_util::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+    R: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    let frame = CloseFrame {
+        code: CloseCode::Normal,
+        reason: std::borrow::Cow::Borrowed("kiosk shutdown"),
+    };
+
+    if let Err(e) = write.send(Message::Close(Some(frame))).await {
+        if is_send_after_closing(&e) {
+            log::debug!("WebSocket already closing, skipping close frame: {}", e);
+        } else {
+            log::warn!("Failed to send WebSocket close frame: {}", e);
+        }
+    }
+
+    match tokio::time::timeout(
+        std::time::Duration::from_millis(config::WS_CLOSE_ACK_TIMEOUT_MS),
+        read.next(),
+    )
+    .await
+    {
+        Ok(Some(Ok(Message::Close(_)))) => log::debug!("Server acknowledged WebSocket close"),
+        Ok(_) => {}
+        Err(_) => log::debug!("Timed out waiting for WebSocket close acknowledgement"),
+    }
+
+    let _ = write.close().await;
+}
+
+/// Whether `err` is tungstenite's `SendAfterClosing`, which happens when we
+/// try to send after the peer already initiated the close handshake
+fn is_send_after_closing(err: &tokio_tungstenite::tungstenite::Error) -> bool {
+    matches!(
+        err,
+        tokio_tungstenite::tungstenite::Error::Protocol(ProtocolError::SendAfterClosing)
+    )
+}
+
+/// Exponential backoff with jitter: `min(base * 2^attempt, cap)` plus up to 20%
+/// extra, so a flaky connection isn't retried at a fixed interval
+fn backoff_delay_ms(policy: &ReconnectPolicy, attempt: u32) -> u64 {
+    let exp = policy.base_delay_ms.saturating_mul(1u64 << attempt.min(20));
+    let capped = exp.min(policy.max_delay_ms);
+    capped.saturating_add((capped as f64 * 0.2 * jitter_fraction()) as u64)
+}
+
+/// A cheap, non-cryptographic jitter source in `[0.0, 1.0)` derived from the
+/// clock, avoiding a dependency on a random number generator crate for a
+/// one-off backoff nudge
+fn jitter_fraction() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    (nanos % 1000) as f64 / 1000.0
+}
+
+/// If `text` carries an `id` matching a pending `WsHandle::request`, complete
+/// it with the full decoded payload and return `true`; otherwise leave it for
+/// `parse_message` to handle as a regular event.
+fn complete_pending_request(
+    text: &str,
+    pending: &mut BTreeMap<u64, oneshot::Sender<serde_json::Value>>,
+) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return false;
+    };
+    let Some(id) = value.get("id").and_then(|v| v.as_u64()) else {
+        return false;
+    };
+    match pending.remove(&id) {
+        Some(respond_to) => {
+            let _ = respond_to.send(value);
+            true
+        }
+        None => false,
+    }
 }
 
 fn parse_message(text: &str) -> Option<WsEvent> {
@@ -188,6 +617,19 @@ fn parse_message(text: &str) -> Option<WsEvent> {
             Some(WsEvent::CaptureFailed(error))
         }
         "session_ended" => Some(WsEvent::SessionEnded),
+        "sdp_answer" => {
+            msg.data
+                .and_then(|d| d.get("sdp").and_then(|v| v.as_str()).map(String::from))
+                .map(WsEvent::SdpAnswer)
+        }
+        "ice_candidate" => msg.data.and_then(|d| {
+            let candidate = d.get("candidate")?.as_str()?.to_string();
+            let sdp_mline_index = d.get("sdpMLineIndex")?.as_u64()? as u32;
+            Some(WsEvent::IceCandidate {
+                candidate,
+                sdp_mline_index,
+            })
+        }),
         _ => {
             log::warn!("Unknown message type: {}", msg.msg_type);
             None
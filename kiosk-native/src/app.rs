@@ -1,23 +1,105 @@
 //! Application context - bridges the GTK-free state machine with GTK UI.
 
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use gtk4 as gtk;
 use tokio::sync::mpsc;
 
-use crate::api::{ApiClient, WsEvent, WsHandle};
+use crate::api::{ApiClient, MediaKind, WsEvent, WsHandle};
+use crate::config::Config;
 use crate::state::{KioskCommand, KioskEvent, KioskStateMachine};
+use crate::video::pipeline::SignalMessage;
 use crate::video::VideoPipeline;
 
+/// Number of decoded lightbox textures kept around so flicking back to a
+/// recently-viewed photo doesn't re-fetch and re-decode it.
+const TEXTURE_CACHE_CAPACITY: usize = 6;
+
+/// Number of decoded session-screen textures (strip thumbnails and the main
+/// photo view) kept around, so an incremental `update_photos` rebuild only
+/// decodes the photos it hasn't already shown rather than re-fetching the
+/// whole strip.
+const SESSION_TEXTURE_CACHE_CAPACITY: usize = 128;
+
+/// Bounded LRU cache of decoded textures, keyed by URL.
+///
+/// `load_lightbox_image` consults the lightbox's instance of this before
+/// spawning a fetch, and the lightbox preloads neighboring photos into it so
+/// navigation feels instant; it's cleared when the lightbox is closed so the
+/// decoded bitmaps don't linger on memory-limited kiosk hardware. The session
+/// screen's instance has no such clear point - it lives for the whole
+/// session and is sized to hold the entire strip comfortably.
+pub struct TextureCache {
+    entries: RefCell<VecDeque<(String, gtk::gdk::Texture)>>,
+    capacity: usize,
+}
+
+impl TextureCache {
+    /// Build an empty cache holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: RefCell::new(VecDeque::new()),
+            capacity,
+        }
+    }
 
+    /// Look up a cached texture, moving it to the front (most-recently-used)
+    /// on a hit.
+    pub fn get(&self, url: &str) -> Option<gtk::gdk::Texture> {
+        let mut entries = self.entries.borrow_mut();
+        let pos = entries
+            .iter()
+            .position(|(cached_url, _)| cached_url == url)?;
+        let (_, texture) = entries.remove(pos).expect("position just checked");
+        entries.push_front((url.to_string(), texture.clone()));
+        Some(texture)
+    }
+
+    /// Insert a freshly-decoded texture, evicting the least-recently-used
+    /// entry once the cache is over capacity.
+    pub fn insert(&self, url: String, texture: gtk::gdk::Texture) {
+        let mut entries = self.entries.borrow_mut();
+        entries.retain(|(cached_url, _)| cached_url != &url);
+        entries.push_front((url, texture));
+        entries.truncate(self.capacity);
+    }
+
+    /// Drop every cached texture, e.g. when the lightbox is closed.
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self::new(TEXTURE_CACHE_CAPACITY)
+    }
+}
 
 /// Messages sent from async tasks to the GTK main loop
 #[derive(Debug, Clone)]
 pub enum AppMessage {
     /// Process a kiosk event through the state machine
     Event(KioskEvent),
+    /// An outgoing WebRTC signalling message from the preview pipeline's
+    /// `on_signal` callback (fired on a GStreamer thread), to forward over
+    /// the session's `WsHandle` once back on the GTK main loop
+    SendSignal(SignalMessage),
+    /// A remote SDP answer, received over the session's WebSocket, to apply
+    /// to the preview pipeline once back on the GTK main loop
+    RemoteSdpAnswer(String),
+    /// A remote ICE candidate, received over the session's WebSocket, to feed
+    /// into the preview pipeline once back on the GTK main loop
+    RemoteIceCandidate {
+        candidate: String,
+        sdp_mline_index: u32,
+    },
+    /// A QR payload decoded by a `widgets::ScannerPanel`'s camera pipeline
+    /// (fired on a GStreamer thread), to route once back on the GTK main loop
+    QrScanned(String),
 }
 
 /// Sender that can dispatch messages to the GTK main loop from any thread
@@ -39,6 +121,8 @@ pub struct AppContext {
     pub state_machine: RefCell<KioskStateMachine>,
     /// HTTP API client
     pub api: ApiClient,
+    /// Resolved backend configuration (host, QR sizes, WebSocket timeouts)
+    pub config: Config,
     /// GStreamer video pipeline
     pub video: RefCell<Option<VideoPipeline>>,
     /// Tokio runtime for async operations
@@ -47,19 +131,30 @@ pub struct AppContext {
     pub message_tx: MessageSender,
     /// WebSocket handle (for cleanup)
     ws_handle: RefCell<Option<WsHandle>>,
+    /// LRU cache of decoded lightbox textures, keyed by `web_url`
+    pub texture_cache: TextureCache,
+    /// LRU cache of decoded session-screen textures (strip thumbnails and the
+    /// main photo view), keyed by URL
+    pub session_texture_cache: TextureCache,
 }
 
 impl AppContext {
-    pub fn new(runtime: Arc<tokio::runtime::Runtime>) -> (Rc<Self>, mpsc::UnboundedReceiver<AppMessage>) {
+    pub fn new(
+        runtime: Arc<tokio::runtime::Runtime>,
+        config: Config,
+    ) -> (Rc<Self>, mpsc::UnboundedReceiver<AppMessage>) {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let ctx = Rc::new(Self {
             state_machine: RefCell::new(KioskStateMachine::new()),
-            api: ApiClient::new(),
+            api: ApiClient::new(config.clone()),
+            config,
             video: RefCell::new(None),
             runtime,
             message_tx: MessageSender { tx },
             ws_handle: RefCell::new(None),
+            texture_cache: TextureCache::default(),
+            session_texture_cache: TextureCache::new(SESSION_TEXTURE_CACHE_CAPACITY),
         });
 
         (ctx, rx)
@@ -70,6 +165,18 @@ impl AppContext {
         let pipeline = VideoPipeline::new()?;
         let paintable = pipeline.paintable().clone();
 
+        // Tell the state machine when the live feed drops to/returns from the
+        // fallback placeholder, so it can block capture while unavailable
+        let tx = self.message_tx.clone();
+        pipeline.on_fallback_changed(move |showing_fallback| {
+            let event = if showing_fallback {
+                KioskEvent::CameraFailed
+            } else {
+                KioskEvent::CameraRecovered
+            };
+            tx.send(AppMessage::Event(event));
+        });
+
         // Set up error handling with automatic reconnection
         pipeline.setup_bus_watch_with_reconnect();
 
@@ -84,6 +191,123 @@ impl AppContext {
         self.message_tx.send(AppMessage::Event(event));
     }
 
+    /// Forward an outgoing WebRTC signalling message over the session's
+    /// `WsHandle`. Called from the GTK main loop after an `AppMessage::SendSignal`
+    /// bounces a pipeline `on_signal` callback off its GStreamer thread.
+    pub fn forward_signal(&self, msg: SignalMessage) {
+        let ws_handle = self.ws_handle.borrow();
+        let Some(handle) = ws_handle.as_ref() else {
+            return;
+        };
+        match msg {
+            SignalMessage::Offer(sdp) => handle.send_sdp_offer(&sdp),
+            SignalMessage::IceCandidate {
+                candidate,
+                sdp_mline_index,
+            } => handle.send_ice_candidate(&candidate, sdp_mline_index),
+        }
+    }
+
+    /// Apply a remote SDP answer to the preview pipeline, surfacing a
+    /// `PreviewConnectionFailed` event on failure
+    pub fn apply_remote_sdp_answer(&self, sdp: &str) {
+        if let Some(pipeline) = self.video.borrow().as_ref() {
+            if let Err(e) = pipeline.set_remote_answer(sdp) {
+                self.send_event(KioskEvent::PreviewConnectionFailed {
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Feed a remote ICE candidate into the preview pipeline
+    pub fn apply_remote_ice_candidate(&self, candidate: &str, sdp_mline_index: u32) {
+        if let Some(pipeline) = self.video.borrow().as_ref() {
+            pipeline.add_ice_candidate(sdp_mline_index, candidate);
+        }
+    }
+
+    /// Route a QR payload decoded by a `widgets::ScannerPanel`: a scanned
+    /// session join URL claims that session on this kiosk exactly as if the
+    /// backend had just created it, while a `PICPOP-CFG:` payload persists
+    /// its fields as overrides for next startup (`config` is resolved once
+    /// into a process-wide static, so a scan can't change this run).
+    pub fn handle_scanned_qr(self: &Rc<Self>, text: &str) {
+        if let Some(payload) = text.strip_prefix("PICPOP-CFG:") {
+            self.apply_scanned_config(payload);
+            return;
+        }
+
+        let join_prefix = format!("{}/join/", self.config.api_base);
+        if let Some(session_id) = text.strip_prefix(join_prefix.as_str()) {
+            self.send_event(KioskEvent::SessionCreated {
+                id: session_id.to_string(),
+                join_url: text.to_string(),
+            });
+            return;
+        }
+
+        log::error!("Scanned QR payload not recognized: {}", text);
+    }
+
+    /// Parse a `pin=...;wifi_ssid=...;wifi_password=...;server=...` payload
+    /// and persist whichever of the non-`pin` fields it sets to the on-disk
+    /// config file. Fields are named after `Config`'s own (e.g.
+    /// `PICPOP_WIFI_SSID` has the same `wifi_ssid` root), rather than
+    /// bundling SSID/password into one comma-joined field, since either can
+    /// itself contain a comma.
+    ///
+    /// This reconfigures WiFi credentials and the backend host and can only
+    /// be undone by another scan or manual edit of the config file, so it
+    /// requires `pin` to match the operator passcode configured as
+    /// `setup_pin` before anything is persisted - a kiosk sitting on a show
+    /// floor is touchable by anyone, not just staff.
+    fn apply_scanned_config(&self, payload: &str) {
+        let mut pin = None;
+        let mut wifi_ssid = None;
+        let mut wifi_password = None;
+        let mut api_base = None;
+
+        for field in payload.split(';').filter(|f| !f.is_empty()) {
+            let Some((key, value)) = field.split_once('=') else {
+                log::warn!("Ignoring malformed scanned config field: {}", field);
+                continue;
+            };
+            match key {
+                "pin" => pin = Some(value.to_string()),
+                "wifi_ssid" => wifi_ssid = Some(value.to_string()),
+                "wifi_password" => wifi_password = Some(value.to_string()),
+                "server" => api_base = Some(value.to_string()),
+                _ => log::warn!("Ignoring unknown scanned config field: {}", key),
+            }
+        }
+
+        match self.config.setup_pin.as_deref() {
+            None => {
+                log::error!(
+                    "Rejecting scanned configuration - no setup_pin is configured for this kiosk"
+                );
+                return;
+            }
+            Some(expected) if pin.as_deref() != Some(expected) => {
+                log::error!("Rejecting scanned configuration - pin did not match");
+                return;
+            }
+            Some(_) => {}
+        }
+
+        match crate::config::persist_scanned_override(
+            wifi_ssid.as_deref(),
+            wifi_password.as_deref(),
+            api_base.as_deref(),
+        ) {
+            Ok(()) => {
+                log::info!("Applied scanned configuration - restart the kiosk for it to take effect")
+            }
+            Err(e) => log::error!("Failed to persist scanned configuration: {}", e),
+        }
+    }
+
     /// Process an event and execute resulting commands
     /// This should be called from the GTK main loop
     pub fn process_event(self: &Rc<Self>, event: KioskEvent) -> Vec<KioskCommand> {
@@ -107,8 +331,10 @@ impl AppContext {
                 self.runtime.spawn(async move {
                     match api.create_session().await {
                         Ok(response) => {
+                            let join_url = crate::config::join_url(&response.id);
                             tx.send(AppMessage::Event(KioskEvent::SessionCreated {
                                 id: response.id,
+                                join_url,
                             }));
                         }
                         Err(e) => {
@@ -138,12 +364,12 @@ impl AppContext {
                 });
             }
 
-            KioskCommand::TriggerCapture { session_id } => {
+            KioskCommand::TriggerCapture { session_id, mode } => {
                 let tx = self.message_tx.clone();
                 let api = self.api.clone();
 
                 self.runtime.spawn(async move {
-                    if let Err(e) = api.capture(&session_id).await {
+                    if let Err(e) = api.capture(&session_id, mode.as_str()).await {
                         tx.send(AppMessage::Event(KioskEvent::CaptureFailed {
                             error: e.to_string(),
                         }));
@@ -152,23 +378,106 @@ impl AppContext {
                 });
             }
 
+            KioskCommand::DeletePhoto {
+                session_id,
+                photo_id,
+            } => {
+                let tx = self.message_tx.clone();
+                let api = self.api.clone();
+
+                self.runtime.spawn(async move {
+                    match api.delete_photo(&session_id, &photo_id).await {
+                        Ok(()) => {
+                            tx.send(AppMessage::Event(KioskEvent::PhotoDeleted { photo_id }));
+                        }
+                        Err(e) => {
+                            log::error!("Failed to delete photo {}: {}", photo_id, e);
+                            tx.send(AppMessage::Event(KioskEvent::PhotoActionFailed {
+                                error: e.to_string(),
+                            }));
+                        }
+                    }
+                });
+            }
+
+            KioskCommand::MarkPhotoForPrint {
+                session_id,
+                photo_id,
+            } => {
+                let tx = self.message_tx.clone();
+                let api = self.api.clone();
+
+                self.runtime.spawn(async move {
+                    if let Err(e) = api.mark_photo_for_print(&session_id, &photo_id).await {
+                        log::error!("Failed to mark photo {} for print: {}", photo_id, e);
+                        tx.send(AppMessage::Event(KioskEvent::PhotoActionFailed {
+                            error: e.to_string(),
+                        }));
+                    }
+                });
+            }
+
+            KioskCommand::UploadEditedPhoto {
+                session_id,
+                photo_id,
+                bytes,
+            } => {
+                let tx = self.message_tx.clone();
+                let api = self.api.clone();
+
+                self.runtime.spawn(async move {
+                    match api.upload_edited_photo(&session_id, &photo_id, bytes).await {
+                        Ok(photo) => {
+                            tx.send(AppMessage::Event(KioskEvent::PhotoUpdated { photo }));
+                        }
+                        Err(e) => {
+                            log::error!("Failed to upload edited photo {}: {}", photo_id, e);
+                            tx.send(AppMessage::Event(KioskEvent::PhotoActionFailed {
+                                error: e.to_string(),
+                            }));
+                        }
+                    }
+                });
+            }
+
             KioskCommand::ConnectWebSocket { session_id } => {
                 let tx = self.message_tx.clone();
-                let runtime = self.runtime.clone();
 
                 // Connect to WebSocket with a callback that dispatches events
-                let handle = crate::api::websocket::connect(runtime, session_id, move |ws_event| {
+                let handle = crate::api::websocket::connect(session_id, None, move |ws_event| {
                     let event = match ws_event {
                         WsEvent::Connected => KioskEvent::WebSocketConnected,
-                        WsEvent::Disconnected => KioskEvent::WebSocketDisconnected,
+                        WsEvent::Disconnected { attempt } => {
+                            KioskEvent::WebSocketDisconnected { attempt }
+                        }
                         WsEvent::PhoneConnected => KioskEvent::PhoneConnected,
                         WsEvent::PhoneDisconnected => KioskEvent::PhoneDisconnected,
                         WsEvent::Countdown(value) => KioskEvent::CountdownTick { value },
-                        WsEvent::PhotoReady(photo) => KioskEvent::PhotoReady { photo },
+                        WsEvent::PhotoReady(photo) => match photo.kind {
+                            MediaKind::Image => KioskEvent::PhotoReady { photo },
+                            MediaKind::Clip => KioskEvent::ClipReady { clip: photo },
+                        },
                         WsEvent::Processing => KioskEvent::Processing,
                         WsEvent::CaptureComplete => KioskEvent::CaptureComplete,
                         WsEvent::CaptureFailed(error) => KioskEvent::CaptureFailed { error },
                         WsEvent::SessionEnded => KioskEvent::SessionEnded,
+                        // Preview signalling bypasses the state machine - it's
+                        // plumbing for `ConnectPreview`/`DisconnectPreview`, not
+                        // app state - so it's dispatched as its own `AppMessage`
+                        WsEvent::SdpAnswer(sdp) => {
+                            tx.send(AppMessage::RemoteSdpAnswer(sdp));
+                            return;
+                        }
+                        WsEvent::IceCandidate {
+                            candidate,
+                            sdp_mline_index,
+                        } => {
+                            tx.send(AppMessage::RemoteIceCandidate {
+                                candidate,
+                                sdp_mline_index,
+                            });
+                            return;
+                        }
                     };
                     tx.send(AppMessage::Event(event));
                 });
@@ -185,6 +494,36 @@ impl AppContext {
                 }
             }
 
+            KioskCommand::ConnectPreview { session_id: _ } => {
+                // The WebRTC pipeline (when built with `PreviewTransport::WebRtc`)
+                // already auto-generates its SDP offer/ICE candidates as soon as
+                // `init_video` creates it, but has nowhere to send them until a
+                // session's WebSocket exists - register that forwarding now.
+                // `on_signal` fires on a GStreamer thread, so bounce through
+                // `message_tx` back to the GTK main loop rather than touching
+                // `self.ws_handle` (a `RefCell`, not `Sync`) directly here.
+                if let Some(pipeline) = self.video.borrow().as_ref() {
+                    let tx = self.message_tx.clone();
+                    pipeline.on_signal(move |msg| {
+                        tx.send(AppMessage::SendSignal(msg));
+                    });
+                } else {
+                    self.message_tx
+                        .send(AppMessage::Event(KioskEvent::PreviewConnectionFailed {
+                            error: "no video pipeline to signal".to_string(),
+                        }));
+                }
+            }
+
+            KioskCommand::DisconnectPreview => {
+                // Stop forwarding signalling for the ended session; the
+                // pipeline itself keeps running (showing its fallback) for
+                // the next session's `ConnectPreview` to re-register against.
+                if let Some(pipeline) = self.video.borrow().as_ref() {
+                    pipeline.on_signal(|_| {});
+                }
+            }
+
             KioskCommand::ScheduleErrorClear => {
                 let tx = self.message_tx.clone();
                 glib::timeout_add_once(
@@ -195,6 +534,28 @@ impl AppContext {
                 );
             }
 
+            KioskCommand::RenderJoinQr { .. } => {
+                // The QR bitmap itself is rendered by the window from
+                // `state_machine.join_url()` after processing events
+            }
+
+            KioskCommand::StartAttractLoop | KioskCommand::StopAttractLoop => {
+                // The attract-mode loop itself is rendered by the window from
+                // `state_machine.attract_mode_active` after processing events
+            }
+
+            KioskCommand::ReportStreamInfo => {
+                if let Some(info) = self.video.borrow().as_ref().and_then(|p| p.stream_info()) {
+                    self.message_tx
+                        .send(AppMessage::Event(KioskEvent::StreamInfo {
+                            width: info.width,
+                            height: info.height,
+                            codec: info.codec,
+                            bitrate: info.bitrate,
+                        }));
+                }
+            }
+
             KioskCommand::UpdateUI => {
                 // This is handled by the window after processing events
             }
@@ -1,64 +1,529 @@
-//! Configuration constants for the PicPop kiosk.
+//! Configuration for the PicPop kiosk.
+//!
+//! The backend host, QR sizes, and WebSocket timeouts are resolved once at
+//! startup (by `Config::load`, called from `main`) so the kiosk can target a
+//! real backend without recompiling. Priority, highest first: CLI flags,
+//! `PICPOP_*` environment variables, an optional TOML file in the user config
+//! dir, then the defaults below. Everything else in this module (camera
+//! pipeline defaults, idle-tick thresholds, cache sizing) stays a compile-time
+//! constant - it's not the kind of thing a deployment needs to override.
 
-/// Base URL for HTTP API calls
-pub const API_BASE: &str = "http://localhost:8000";
+use std::sync::OnceLock;
 
-/// Base URL for WebSocket connections
-pub const WS_BASE: &str = "ws://localhost:8000";
+use serde::{Deserialize, Serialize};
 
-/// Camera preview endpoint
-pub const CAMERA_PREVIEW_URL: &str = "http://localhost:8000/api/v1/camera/preview";
+/// Camera preview endpoint's path, joined onto the configured `api_base`
+const CAMERA_PREVIEW_PATH: &str = "/api/v1/camera/preview";
 
-/// QR code size in pixels (small, for collapsed view)
-/// Must be at least ~150px for reliable scanning of version 6 QR codes
-pub const QR_SIZE_SMALL: u32 = 150;
-/// QR code size in pixels (large, for expanded view)
-pub const QR_SIZE_LARGE: u32 = 280;
+/// Transport used by `VideoPipeline` for the live camera preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewTransport {
+    /// MJPEG over HTTP via `souphttpsrc`/`multipartdemux` (default)
+    #[default]
+    Mjpeg,
+    /// Low-latency WebRTC stream via `webrtcbin`, signalled over the kiosk WebSocket
+    WebRtc,
+}
+
+/// Which transport `VideoPipeline::new()` should build
+pub const PREVIEW_TRANSPORT: PreviewTransport = PreviewTransport::Mjpeg;
 
-/// WebSocket reconnection delay in milliseconds
-pub const WS_RECONNECT_DELAY_MS: u64 = 2000;
+/// STUN server used by the WebRTC preview transport to gather ICE candidates
+pub const STUN_SERVER: &str = "stun://stun.l.google.com:19302";
+
+/// How long `WsHandle::request` waits for a matching response before giving up
+pub const WS_REQUEST_TIMEOUT_MS: u64 = 10_000;
+
+/// How long to wait for the server to acknowledge a client-initiated close
+/// frame before tearing the socket down anyway
+pub const WS_CLOSE_ACK_TIMEOUT_MS: u64 = 1_000;
 
 /// Error message display duration in milliseconds
 pub const ERROR_DISPLAY_DURATION_MS: u64 = 5000;
 
+/// Number of `IdleTick` events (fed at the main loop's ~16ms cadence) of no
+/// activity before the welcome screen starts its attract-mode loop (~30s)
+pub const ATTRACT_MODE_IDLE_TICKS: u64 = 1875;
+
+/// Number of `IdleTick` events of no activity before an in-progress session
+/// is automatically ended and the kiosk resets to the welcome screen (~2min)
+pub const SESSION_IDLE_TIMEOUT_TICKS: u64 = 7500;
+
+/// Photos from ended sessions kept around for the attract-mode slideshow;
+/// once exceeded, the oldest are dropped
+pub const ATTRACT_PHOTO_HISTORY_LIMIT: usize = 12;
+
+/// Byte budget for `ApiClient`'s in-memory image cache tier. Once exceeded,
+/// least-recently-used entries are evicted (the on-disk tier is unbounded).
+pub const IMAGE_CACHE_MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// Directory (relative to the working directory) holding the on-disk image
+/// cache tier, keyed by a hash of each image's URL.
+pub const IMAGE_CACHE_DIR: &str = ".picpop-cache/images";
+
+/// Runtime-overridable backend address and tunables, resolved once by
+/// `Config::load` and installed process-wide via `init`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Base URL for HTTP API calls, e.g. `http://localhost:8000`
+    pub api_base: String,
+    /// Base URL for WebSocket connections, e.g. `ws://localhost:8000`
+    pub ws_base: String,
+    /// QR code size in pixels (small, for collapsed view). Must be at least
+    /// ~150px for reliable scanning of version 6 QR codes.
+    pub qr_size_small: u32,
+    /// QR code size in pixels (large, for expanded view)
+    pub qr_size_large: u32,
+    /// Base WebSocket reconnect delay before backoff, in milliseconds
+    pub ws_reconnect_base_delay_ms: u64,
+    /// Ceiling the exponential WebSocket reconnect backoff is capped at, in milliseconds
+    pub ws_reconnect_max_delay_ms: u64,
+    /// Interval between client-initiated WebSocket pings, in milliseconds
+    pub ws_heartbeat_interval_ms: u64,
+    /// Maximum time without any inbound WebSocket frame (including a pong)
+    /// before the connection is treated as dead and torn down for reconnect,
+    /// in milliseconds
+    pub ws_liveness_timeout_ms: u64,
+    /// SSID of the WiFi network advertised by the welcome screen's QR code
+    pub wifi_ssid: String,
+    /// Password of the WiFi network advertised by the welcome screen's QR code
+    pub wifi_password: String,
+    /// Operator passcode required in a scanned `PICPOP-CFG:` setup QR code
+    /// before its fields are persisted (see `persist_scanned_override`).
+    /// `None` means setup-by-QR is disabled entirely - there's no sensible
+    /// default passcode to fall back to.
+    pub setup_pin: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            api_base: "http://localhost:8000".to_string(),
+            ws_base: "ws://localhost:8000".to_string(),
+            qr_size_small: 150,
+            qr_size_large: 280,
+            ws_reconnect_base_delay_ms: 2000,
+            ws_reconnect_max_delay_ms: 30_000,
+            ws_heartbeat_interval_ms: 15_000,
+            ws_liveness_timeout_ms: 45_000,
+            wifi_ssid: String::new(),
+            wifi_password: String::new(),
+            setup_pin: None,
+        }
+    }
+}
+
+/// Partial overrides read from the optional TOML config file - every field
+/// is optional so the file only needs to mention what it's overriding.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ConfigFile {
+    api_base: Option<String>,
+    ws_base: Option<String>,
+    qr_size_small: Option<u32>,
+    qr_size_large: Option<u32>,
+    ws_reconnect_base_delay_ms: Option<u64>,
+    ws_reconnect_max_delay_ms: Option<u64>,
+    ws_heartbeat_interval_ms: Option<u64>,
+    ws_liveness_timeout_ms: Option<u64>,
+    wifi_ssid: Option<String>,
+    wifi_password: Option<String>,
+    setup_pin: Option<String>,
+}
+
+impl Config {
+    /// Resolve a `Config` from defaults, the optional TOML file, environment
+    /// variables, and `argv`, in increasing priority order. Call once from
+    /// `main`, before anything else in this module is used, and pass the
+    /// result to `init`.
+    pub fn load() -> Self {
+        let mut config = Self::default();
+        config.apply_file(&config_file_path());
+        config.apply_env();
+        config.apply_args(std::env::args().skip(1));
+        config
+    }
+
+    fn apply_file(&mut self, path: &std::path::Path) {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return;
+        };
+        let file: ConfigFile = match toml::from_str(&contents) {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("Ignoring malformed config file {}: {}", path.display(), e);
+                return;
+            }
+        };
+
+        if let Some(v) = file.api_base {
+            self.api_base = v;
+        }
+        if let Some(v) = file.ws_base {
+            self.ws_base = v;
+        }
+        if let Some(v) = file.qr_size_small {
+            self.qr_size_small = v;
+        }
+        if let Some(v) = file.qr_size_large {
+            self.qr_size_large = v;
+        }
+        if let Some(v) = file.ws_reconnect_base_delay_ms {
+            self.ws_reconnect_base_delay_ms = v;
+        }
+        if let Some(v) = file.ws_reconnect_max_delay_ms {
+            self.ws_reconnect_max_delay_ms = v;
+        }
+        if let Some(v) = file.ws_heartbeat_interval_ms {
+            self.ws_heartbeat_interval_ms = v;
+        }
+        if let Some(v) = file.ws_liveness_timeout_ms {
+            self.ws_liveness_timeout_ms = v;
+        }
+        if let Some(v) = file.wifi_ssid {
+            self.wifi_ssid = v;
+        }
+        if let Some(v) = file.wifi_password {
+            self.wifi_password = v;
+        }
+        if let Some(v) = file.setup_pin {
+            self.setup_pin = Some(v);
+        }
+    }
+
+    fn apply_env(&mut self) {
+        if let Ok(v) = std::env::var("PICPOP_API_BASE") {
+            self.api_base = v;
+        }
+        if let Ok(v) = std::env::var("PICPOP_WS_BASE") {
+            self.ws_base = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_QR_SIZE_SMALL") {
+            self.qr_size_small = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_QR_SIZE_LARGE") {
+            self.qr_size_large = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_WS_RECONNECT_BASE_DELAY_MS") {
+            self.ws_reconnect_base_delay_ms = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_WS_RECONNECT_MAX_DELAY_MS") {
+            self.ws_reconnect_max_delay_ms = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_WS_HEARTBEAT_INTERVAL_MS") {
+            self.ws_heartbeat_interval_ms = v;
+        }
+        if let Some(v) = env_parsed("PICPOP_WS_LIVENESS_TIMEOUT_MS") {
+            self.ws_liveness_timeout_ms = v;
+        }
+        if let Ok(v) = std::env::var("PICPOP_WIFI_SSID") {
+            self.wifi_ssid = v;
+        }
+        if let Ok(v) = std::env::var("PICPOP_WIFI_PASSWORD") {
+            self.wifi_password = v;
+        }
+        if let Ok(v) = std::env::var("PICPOP_SETUP_PIN") {
+            self.setup_pin = Some(v);
+        }
+    }
+
+    /// Apply `--api-base <url>` / `--ws-base <url>` CLI flags, the highest
+    /// priority override
+    fn apply_args(&mut self, args: impl Iterator<Item = String>) {
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--api-base" => {
+                    if let Some(v) = args.next() {
+                        self.api_base = v;
+                    }
+                }
+                "--ws-base" => {
+                    if let Some(v) = args.next() {
+                        self.ws_base = v;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Build the sessions API URL
+    pub fn sessions_url(&self) -> String {
+        format!("{}/api/v1/sessions", self.api_base)
+    }
+
+    /// Build the session end URL
+    pub fn session_end_url(&self, session_id: &str) -> String {
+        format!("{}/api/v1/sessions/{}/end", self.api_base, session_id)
+    }
+
+    /// Build the capture URL for the given capture mode ("photo" or "clip")
+    pub fn capture_url(&self, session_id: &str, mode: &str) -> String {
+        format!(
+            "{}/api/v1/sessions/{}/capture?mode={}",
+            self.api_base, session_id, mode
+        )
+    }
+
+    /// Build the URL to delete a photo from a session
+    pub fn photo_delete_url(&self, session_id: &str, photo_id: &str) -> String {
+        format!(
+            "{}/api/v1/sessions/{}/photos/{}",
+            self.api_base, session_id, photo_id
+        )
+    }
+
+    /// Build the URL to mark a photo for printing
+    pub fn photo_print_url(&self, session_id: &str, photo_id: &str) -> String {
+        format!(
+            "{}/api/v1/sessions/{}/photos/{}/print",
+            self.api_base, session_id, photo_id
+        )
+    }
+
+    /// Build the URL to upload a re-encoded edit of a photo
+    pub fn photo_edit_url(&self, session_id: &str, photo_id: &str) -> String {
+        format!(
+            "{}/api/v1/sessions/{}/photos/{}/edit",
+            self.api_base, session_id, photo_id
+        )
+    }
+
+    /// Build the WiFi QR URL
+    pub fn wifi_qr_url(&self, size: u32) -> String {
+        format!("{}/api/v1/sessions/wifi-qr?size={}", self.api_base, size)
+    }
+
+    /// Build the session QR URL
+    pub fn session_qr_url(&self, session_id: &str, size: u32) -> String {
+        format!(
+            "{}/api/v1/sessions/{}/qr?size={}",
+            self.api_base, session_id, size
+        )
+    }
+
+    /// Build the join URL phones scan (via the session QR) to pair with a session
+    pub fn join_url(&self, session_id: &str) -> String {
+        format!("{}/join/{}", self.api_base, session_id)
+    }
+
+    /// Build the WebSocket URL for a session
+    pub fn ws_url(&self, session_id: &str) -> String {
+        format!("{}/api/v1/ws/kiosk/{}", self.ws_base, session_id)
+    }
+
+    /// Build full URL for a photo path
+    pub fn photo_url(&self, path: &str) -> String {
+        if path.starts_with("http") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.api_base, path)
+        }
+    }
+
+    /// Build the camera preview URL
+    pub fn camera_preview_url(&self) -> String {
+        format!("{}{}", self.api_base, CAMERA_PREVIEW_PATH)
+    }
+
+    /// Build the `WIFI:T:WPA;S:<ssid>;P:<pass>;;` credential string encoded
+    /// into the welcome screen's local WiFi QR code
+    pub fn wifi_credential_string(&self) -> String {
+        format!(
+            "WIFI:T:WPA;S:{};P:{};;",
+            escape_wifi_field(&self.wifi_ssid),
+            escape_wifi_field(&self.wifi_password)
+        )
+    }
+}
+
+/// Escape the characters the WiFi QR spec treats as field separators
+/// (`\`, `;`, `,`, `:`) so an SSID/password containing them round-trips
+fn escape_wifi_field(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn env_parsed<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+/// Where to look for the optional TOML config file: `$PICPOP_CONFIG_FILE` if
+/// set, else `kiosk.toml` under `$XDG_CONFIG_HOME/picpop` or `~/.config/picpop`
+fn config_file_path() -> std::path::PathBuf {
+    if let Ok(path) = std::env::var("PICPOP_CONFIG_FILE") {
+        return std::path::PathBuf::from(path);
+    }
+
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|_| {
+            std::env::var("HOME").map(|home| std::path::PathBuf::from(home).join(".config"))
+        })
+        .unwrap_or_else(|_| std::path::PathBuf::from(".config"));
+
+    config_dir.join("picpop").join("kiosk.toml")
+}
+
+/// Merge scanned override fields (any of which may be absent) into the
+/// on-disk TOML config file, preserving whatever it already overrides.
+/// `Config` is resolved once into the process-wide `CONFIG` static, so this
+/// can't change the running kiosk's behavior - only what `apply_file` reads
+/// back in on the next restart. Used by a `widgets::ScannerPanel` scanning a
+/// `PICPOP-CFG:` setup QR code.
+pub fn persist_scanned_override(
+    wifi_ssid: Option<&str>,
+    wifi_password: Option<&str>,
+    api_base: Option<&str>,
+) -> std::io::Result<()> {
+    let path = config_file_path();
+
+    let mut file: ConfigFile = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    if let Some(v) = wifi_ssid {
+        file.wifi_ssid = Some(v.to_string());
+    }
+    if let Some(v) = wifi_password {
+        file.wifi_password = Some(v.to_string());
+    }
+    if let Some(v) = api_base {
+        file.api_base = Some(v.to_string());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let serialized = toml::to_string_pretty(&file)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(&path, serialized)
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Install the process-wide `Config` resolved by `Config::load`. Must be
+/// called once, early in `main`, before any of this module's free-function
+/// URL builders run.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// The configured backend HTTP base, e.g. `http://localhost:8000`
+pub fn api_base() -> String {
+    get().api_base.clone()
+}
+
 /// Build the sessions API URL
 pub fn sessions_url() -> String {
-    format!("{}/api/v1/sessions", API_BASE)
+    get().sessions_url()
 }
 
 /// Build the session end URL
 pub fn session_end_url(session_id: &str) -> String {
-    format!("{}/api/v1/sessions/{}/end", API_BASE, session_id)
+    get().session_end_url(session_id)
+}
+
+/// Build the capture URL for the given capture mode ("photo" or "clip")
+pub fn capture_url(session_id: &str, mode: &str) -> String {
+    get().capture_url(session_id, mode)
+}
+
+/// Build the URL to delete a photo from a session
+pub fn photo_delete_url(session_id: &str, photo_id: &str) -> String {
+    get().photo_delete_url(session_id, photo_id)
 }
 
-/// Build the capture URL
-pub fn capture_url(session_id: &str) -> String {
-    format!("{}/api/v1/sessions/{}/capture", API_BASE, session_id)
+/// Build the URL to mark a photo for printing
+pub fn photo_print_url(session_id: &str, photo_id: &str) -> String {
+    get().photo_print_url(session_id, photo_id)
+}
+
+/// Build the URL to upload a re-encoded edit of a photo
+pub fn photo_edit_url(session_id: &str, photo_id: &str) -> String {
+    get().photo_edit_url(session_id, photo_id)
 }
 
 /// Build the WiFi QR URL
 pub fn wifi_qr_url(size: u32) -> String {
-    format!("{}/api/v1/sessions/wifi-qr?size={}", API_BASE, size)
+    get().wifi_qr_url(size)
 }
 
 /// Build the session QR URL
 pub fn session_qr_url(session_id: &str, size: u32) -> String {
-    format!(
-        "{}/api/v1/sessions/{}/qr?size={}",
-        API_BASE, session_id, size
-    )
+    get().session_qr_url(session_id, size)
+}
+
+/// Build the join URL phones scan (via the session QR) to pair with a session
+pub fn join_url(session_id: &str) -> String {
+    get().join_url(session_id)
 }
 
 /// Build the WebSocket URL for a session
 pub fn ws_url(session_id: &str) -> String {
-    format!("{}/api/v1/ws/kiosk/{}", WS_BASE, session_id)
+    get().ws_url(session_id)
 }
 
 /// Build full URL for a photo path
 pub fn photo_url(path: &str) -> String {
-    if path.starts_with("http") {
-        path.to_string()
-    } else {
-        format!("{}{}", API_BASE, path)
-    }
+    get().photo_url(path)
+}
+
+/// Build the camera preview URL
+pub fn camera_preview_url() -> String {
+    get().camera_preview_url()
+}
+
+/// Build the `WIFI:T:WPA;S:<ssid>;P:<pass>;;` credential string encoded into
+/// the welcome screen's local WiFi QR code
+pub fn wifi_credential_string() -> String {
+    get().wifi_credential_string()
+}
+
+/// QR code size in pixels (small, for collapsed view)
+pub fn qr_size_small() -> u32 {
+    get().qr_size_small
+}
+
+/// QR code size in pixels (large, for expanded view)
+pub fn qr_size_large() -> u32 {
+    get().qr_size_large
+}
+
+/// Base WebSocket reconnect delay before backoff, in milliseconds
+pub fn ws_reconnect_base_delay_ms() -> u64 {
+    get().ws_reconnect_base_delay_ms
+}
+
+/// Ceiling the exponential WebSocket reconnect backoff is capped at, in milliseconds
+pub fn ws_reconnect_max_delay_ms() -> u64 {
+    get().ws_reconnect_max_delay_ms
+}
+
+/// WebSocket reconnect attempts to give up after, or `None` to retry forever -
+/// phones may stay paired for an entire long-running session, so unlike the
+/// camera preview's `RetryPolicy` this has no default cap
+pub const WS_RECONNECT_MAX_ATTEMPTS: Option<u32> = None;
+
+/// Interval between client-initiated WebSocket pings, in milliseconds
+pub fn ws_heartbeat_interval_ms() -> u64 {
+    get().ws_heartbeat_interval_ms
+}
+
+/// Maximum time without any inbound WebSocket frame (including a pong) before
+/// the connection is treated as dead and torn down for reconnect, in milliseconds
+pub fn ws_liveness_timeout_ms() -> u64 {
+    get().ws_liveness_timeout_ms
 }
@@ -20,6 +20,7 @@ mod ui;
 mod video;
 
 use app::AppContext;
+use state::KioskEvent;
 use ui::MainWindow;
 
 fn main() {
@@ -28,6 +29,12 @@ fn main() {
 
     log::info!("Starting PicPop Kiosk");
 
+    // Resolve backend host, QR sizes, and WebSocket timeouts from CLI flags,
+    // environment variables, and the optional TOML config file, then install
+    // the result process-wide for the rest of the `config` module's builders
+    let config = config::Config::load();
+    config::init(config.clone());
+
     // Create tokio runtime for async operations
     let runtime = Arc::new(
         tokio::runtime::Builder::new_multi_thread()
@@ -49,18 +56,24 @@ fn main() {
 
     app.connect_activate(move |app| {
         // Create application context (includes GTK-free state machine)
-        let (ctx, mut rx) = AppContext::new(runtime_clone.clone());
+        let (ctx, mut rx) = AppContext::new(runtime_clone.clone(), config.clone());
 
         // Create main window (GTK layer)
         let main_window = MainWindow::new(app, ctx.clone());
 
         // Poll the tokio channel from the GTK main loop
         let window = main_window.clone();
+        let idle_ctx = ctx.clone();
         glib::timeout_add_local(std::time::Duration::from_millis(16), move || {
             // Process all pending messages
             while let Ok(msg) = rx.try_recv() {
                 window.handle_message(msg);
             }
+
+            // Feed an idle tick so the state machine can track inactivity and
+            // drive attract mode / auto session reset
+            idle_ctx.send_event(KioskEvent::IdleTick);
+
             glib::ControlFlow::Continue
         });
 
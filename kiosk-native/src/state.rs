@@ -4,7 +4,28 @@
 //! independently of GTK. The UI layer observes state changes and updates
 //! accordingly.
 
-use crate::api::PhotoInfo;
+use crate::api::{MediaKind, PhotoInfo};
+use crate::config;
+
+/// Which kind of media the next `TriggerCapture` should produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureMode {
+    /// A single still photo
+    #[default]
+    Photo,
+    /// A short (2-3s) muted looping "boomerang" clip
+    Clip,
+}
+
+impl CaptureMode {
+    /// The value sent to the capture API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptureMode::Photo => "photo",
+            CaptureMode::Clip => "clip",
+        }
+    }
+}
 
 /// Application states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,8 +36,24 @@ pub enum KioskState {
     Session,
     /// Countdown in progress before capture
     Countdown,
+    /// Capture was just triggered; waiting for the server to start the
+    /// countdown. Brief - exits as soon as the first `CountdownTick` or a
+    /// `CaptureFailed` arrives.
+    Capturing,
     /// Processing photos after capture
     Processing,
+    /// Viewing a photo or clip full-screen, with swipe/keyboard navigation
+    /// between session photos
+    Lightbox,
+}
+
+/// Live pipeline stream diagnostics, for a toggleable on-screen overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+    pub width: i32,
+    pub height: i32,
+    pub codec: String,
+    pub bitrate: u32,
 }
 
 /// Session data (GTK-free)
@@ -25,6 +62,8 @@ pub struct SessionData {
     pub id: String,
     pub phone_count: u32,
     pub photos: Vec<PhotoInfo>,
+    /// URL phones scan (via the QR code) to join this session
+    pub join_url: String,
 }
 
 /// Events that trigger state transitions
@@ -35,28 +74,86 @@ pub enum KioskEvent {
     EndSession,
     TriggerCapture,
 
+    /// Delete a photo from the session, requested from a strip thumbnail's
+    /// context menu
+    DeletePhoto { photo_id: String },
+    /// Mark a photo for printing, requested from a strip thumbnail's context
+    /// menu
+    MarkPhotoForPrint { photo_id: String },
+    /// Upload a re-encoded photo from the photo editor, replacing the
+    /// original on the server
+    UploadEditedPhoto { photo_id: String, bytes: Vec<u8> },
+
     // Photo viewing (in-place, no separate lightbox)
-    SelectPhoto(usize), // View a photo from strip
+    SelectPhoto(usize), // View a photo or clip from strip
     SelectLive,         // Return to live video view
 
+    /// Open the full-screen lightbox on a session photo/clip, from a strip
+    /// thumbnail tap
+    OpenLightbox(usize),
+    /// Close the lightbox and return to the session screen
+    CloseLightbox,
+    /// Swipe/keyboard navigation moved the lightbox to a different photo
+    NavigateLightbox(usize),
+
+    /// Choose whether the next capture produces a photo or a boomerang clip
+    SetCaptureMode(CaptureMode),
+
     // Backend responses
-    SessionCreated { id: String },
+    SessionCreated { id: String, join_url: String },
     SessionCreateFailed { error: String },
     SessionEnded,
 
+    /// A photo was deleted server-side; drop it from the session's photo list
+    PhotoDeleted { photo_id: String },
+    /// An edited photo finished uploading; replace it in the session's photo
+    /// list with the server's version
+    PhotoUpdated { photo: PhotoInfo },
+    /// Delete, mark-for-print, or edit-upload failed server-side
+    PhotoActionFailed { error: String },
+
     // WebSocket events
     PhoneConnected,
     PhoneDisconnected,
     CountdownTick { value: u32 },
     PhotoReady { photo: PhotoInfo },
+    ClipReady { clip: PhotoInfo },
     Processing,
     CaptureComplete,
     CaptureFailed { error: String },
     WebSocketConnected,
-    WebSocketDisconnected,
+    /// The WebSocket dropped and a reconnect is scheduled; `attempt` is the
+    /// 1-indexed reconnect attempt about to run, for a "reconnecting
+    /// (attempt N)" UI indicator
+    WebSocketDisconnected { attempt: u32 },
+
+    /// The WebRTC preview's signalling failed (e.g. SDP/ICE negotiation),
+    /// informational only - the pipeline's own fallback/reconnect handling
+    /// (`CameraFailed`/`CameraRecovered`) still governs capture availability
+    PreviewConnectionFailed { error: String },
+
+    // Camera health
+    /// The live feed dropped and the pipeline switched to the fallback source
+    CameraFailed,
+    /// The live feed came back and the pipeline switched off the fallback
+    CameraRecovered,
 
     // Internal
     ClearError,
+
+    /// Fed periodically by the main loop; advances the idle timer that drives
+    /// attract mode and auto session reset
+    IdleTick,
+    /// Any user touch/input activity, resetting the idle timer
+    UserActivity,
+
+    /// Live pipeline diagnostics, for the toggleable debug overlay
+    StreamInfo {
+        width: i32,
+        height: i32,
+        codec: String,
+        bitrate: u32,
+    },
 }
 
 /// Commands emitted by the state machine for the UI/API layer to execute
@@ -67,13 +164,40 @@ pub enum KioskCommand {
     /// Call the end session API
     EndSession { session_id: String },
     /// Call the capture API
-    TriggerCapture { session_id: String },
+    TriggerCapture {
+        session_id: String,
+        mode: CaptureMode,
+    },
+    /// Call the delete photo API
+    DeletePhoto { session_id: String, photo_id: String },
+    /// Call the mark-for-print API
+    MarkPhotoForPrint { session_id: String, photo_id: String },
+    /// Upload a re-encoded photo from the photo editor
+    UploadEditedPhoto {
+        session_id: String,
+        photo_id: String,
+        bytes: Vec<u8>,
+    },
     /// Connect to WebSocket for session
     ConnectWebSocket { session_id: String },
     /// Disconnect WebSocket
     DisconnectWebSocket,
+    /// Start signalling the low-latency WebRTC preview over the session's
+    /// WebSocket (only takes effect when built with `PreviewTransport::WebRtc`;
+    /// a no-op alongside the default MJPEG transport)
+    ConnectPreview { session_id: String },
+    /// Stop signalling the WebRTC preview for the ended session
+    DisconnectPreview,
     /// Schedule error clear after timeout
     ScheduleErrorClear,
+    /// Render the join-session QR code for the UI to display
+    RenderJoinQr { url: String },
+    /// Start looping the attract-mode highlight reel on the welcome screen
+    StartAttractLoop,
+    /// Stop the attract-mode loop and return to the normal welcome screen
+    StopAttractLoop,
+    /// Read the live pipeline's negotiated caps/tags for the diagnostics overlay
+    ReportStreamInfo,
     /// Update UI to reflect new state
     UpdateUI,
 }
@@ -86,8 +210,29 @@ pub struct KioskStateMachine {
     pub countdown_value: Option<u32>,
     /// Which photo is being viewed (None = live video view)
     pub viewing_photo: Option<usize>,
+    /// Which session photo the full-screen lightbox is showing, while
+    /// `state` is `KioskState::Lightbox` (None otherwise)
+    pub lightbox_index: Option<usize>,
     pub error: Option<String>,
     pub is_loading: bool,
+    /// Whether the live camera feed is currently available. `false` while the
+    /// pipeline is showing its fallback placeholder, which blocks capture.
+    pub camera_available: bool,
+    /// Media type the next `TriggerCapture` will produce
+    pub capture_mode: CaptureMode,
+    /// Ticks since the last activity, counted by `IdleTick`. Drives attract
+    /// mode on the welcome screen and the session auto-reset timeout.
+    idle_ticks: u64,
+    /// Whether the welcome screen is currently showing its attract-mode loop
+    pub attract_mode_active: bool,
+    /// Photos from recently-ended sessions, oldest-first and capped to
+    /// `config::ATTRACT_PHOTO_HISTORY_LIMIT`, for the attract-mode slideshow
+    pub recent_photos: Vec<PhotoInfo>,
+    /// Latest live pipeline diagnostics, for the toggleable debug overlay
+    pub stream_info: Option<StreamInfo>,
+    /// Reconnect attempt currently in progress, or `None` while connected -
+    /// for a "reconnecting (attempt N)" UI indicator
+    pub ws_reconnect_attempt: Option<u32>,
 }
 
 impl Default for KioskStateMachine {
@@ -103,8 +248,16 @@ impl KioskStateMachine {
             session: None,
             countdown_value: None,
             viewing_photo: None,
+            lightbox_index: None,
             error: None,
             is_loading: false,
+            camera_available: true,
+            capture_mode: CaptureMode::default(),
+            idle_ticks: 0,
+            attract_mode_active: false,
+            recent_photos: Vec::new(),
+            stream_info: None,
+            ws_reconnect_attempt: None,
         }
     }
 
@@ -113,10 +266,27 @@ impl KioskStateMachine {
         self.viewing_photo.is_none()
     }
 
+    /// The current session's join URL, so the UI can show its QR code
+    /// prominently while no phones have connected yet
+    pub fn join_url(&self) -> Option<&str> {
+        self.session.as_ref().map(|s| s.join_url.as_str())
+    }
+
     /// Process an event and return commands to execute
     pub fn process(&mut self, event: KioskEvent) -> Vec<KioskCommand> {
         let mut commands = Vec::new();
 
+        // Any real activity resets the idle timer and cancels attract mode;
+        // `IdleTick` is the one event that *advances* it, handled below.
+        if !matches!(event, KioskEvent::IdleTick) {
+            self.idle_ticks = 0;
+            if self.attract_mode_active {
+                self.attract_mode_active = false;
+                commands.push(KioskCommand::StopAttractLoop);
+                commands.push(KioskCommand::UpdateUI);
+            }
+        }
+
         match event {
             KioskEvent::StartSession => {
                 if self.state == KioskState::Welcome && !self.is_loading {
@@ -127,7 +297,7 @@ impl KioskStateMachine {
                 }
             }
 
-            KioskEvent::SessionCreated { id } => {
+            KioskEvent::SessionCreated { id, join_url } => {
                 self.state = KioskState::Session;
                 self.is_loading = false;
                 self.viewing_photo = None;
@@ -135,8 +305,14 @@ impl KioskStateMachine {
                     id: id.clone(),
                     phone_count: 0,
                     photos: Vec::new(),
+                    join_url: join_url.clone(),
+                });
+                commands.push(KioskCommand::ConnectWebSocket {
+                    session_id: id.clone(),
                 });
-                commands.push(KioskCommand::ConnectWebSocket { session_id: id });
+                commands.push(KioskCommand::ConnectPreview { session_id: id });
+                commands.push(KioskCommand::RenderJoinQr { url: join_url });
+                commands.push(KioskCommand::ReportStreamInfo);
                 commands.push(KioskCommand::UpdateUI);
             }
 
@@ -151,29 +327,99 @@ impl KioskStateMachine {
                 if let Some(ref session) = self.session {
                     let session_id = session.id.clone();
                     commands.push(KioskCommand::DisconnectWebSocket);
+                    commands.push(KioskCommand::DisconnectPreview);
                     commands.push(KioskCommand::EndSession { session_id });
                 }
             }
 
             KioskEvent::SessionEnded => {
                 self.state = KioskState::Welcome;
-                self.session = None;
+                if let Some(session) = self.session.take() {
+                    self.recent_photos.extend(session.photos);
+                    let overflow = self
+                        .recent_photos
+                        .len()
+                        .saturating_sub(config::ATTRACT_PHOTO_HISTORY_LIMIT);
+                    if overflow > 0 {
+                        self.recent_photos.drain(0..overflow);
+                    }
+                }
                 self.countdown_value = None;
                 self.viewing_photo = None;
+                self.lightbox_index = None;
                 commands.push(KioskCommand::UpdateUI);
             }
 
             KioskEvent::TriggerCapture => {
-                // Only allow capture from live view in session state
-                if self.state == KioskState::Session && self.is_live_view() {
+                // Only allow capture from live view in session state, and only
+                // while the camera is actually available
+                if self.state == KioskState::Session && self.is_live_view() && self.camera_available
+                {
                     if let Some(ref session) = self.session {
+                        self.state = KioskState::Capturing;
                         commands.push(KioskCommand::TriggerCapture {
                             session_id: session.id.clone(),
+                            mode: self.capture_mode,
                         });
+                        commands.push(KioskCommand::UpdateUI);
                     }
                 }
             }
 
+            KioskEvent::SetCaptureMode(mode) => {
+                self.capture_mode = mode;
+            }
+
+            KioskEvent::DeletePhoto { photo_id } => {
+                if let Some(ref session) = self.session {
+                    commands.push(KioskCommand::DeletePhoto {
+                        session_id: session.id.clone(),
+                        photo_id,
+                    });
+                }
+            }
+
+            KioskEvent::MarkPhotoForPrint { photo_id } => {
+                if let Some(ref session) = self.session {
+                    commands.push(KioskCommand::MarkPhotoForPrint {
+                        session_id: session.id.clone(),
+                        photo_id,
+                    });
+                }
+            }
+
+            KioskEvent::UploadEditedPhoto { photo_id, bytes } => {
+                if let Some(ref session) = self.session {
+                    commands.push(KioskCommand::UploadEditedPhoto {
+                        session_id: session.id.clone(),
+                        photo_id,
+                        bytes,
+                    });
+                }
+            }
+
+            KioskEvent::PhotoDeleted { photo_id } => {
+                if let Some(ref mut session) = self.session {
+                    session.photos.retain(|p| p.id != photo_id);
+                    commands.push(KioskCommand::UpdateUI);
+                }
+            }
+
+            KioskEvent::PhotoUpdated { photo } => {
+                if let Some(ref mut session) = self.session {
+                    if let Some(existing) = session.photos.iter_mut().find(|p| p.id == photo.id) {
+                        *existing = photo;
+                    }
+                    commands.push(KioskCommand::UpdateUI);
+                }
+            }
+
+            KioskEvent::PhotoActionFailed { error } => {
+                self.error = Some(error);
+                commands.push(KioskCommand::ScheduleErrorClear);
+                commands.push(KioskCommand::UpdateUI);
+            }
+
             KioskEvent::PhoneConnected => {
                 if let Some(ref mut session) = self.session {
                     session.phone_count += 1;
@@ -202,6 +448,13 @@ impl KioskStateMachine {
                 }
             }
 
+            KioskEvent::ClipReady { clip } => {
+                if let Some(ref mut session) = self.session {
+                    session.photos.push(clip);
+                    commands.push(KioskCommand::UpdateUI);
+                }
+            }
+
             KioskEvent::Processing => {
                 self.state = KioskState::Processing;
                 self.countdown_value = None;
@@ -241,13 +494,109 @@ impl KioskStateMachine {
                 }
             }
 
+            KioskEvent::OpenLightbox(index) => {
+                if self.state == KioskState::Session {
+                    if let Some(ref session) = self.session {
+                        if index < session.photos.len() {
+                            self.state = KioskState::Lightbox;
+                            self.lightbox_index = Some(index);
+                            commands.push(KioskCommand::UpdateUI);
+                        }
+                    }
+                }
+            }
+
+            KioskEvent::CloseLightbox => {
+                if self.state == KioskState::Lightbox {
+                    self.state = KioskState::Session;
+                    self.lightbox_index = None;
+                    commands.push(KioskCommand::UpdateUI);
+                }
+            }
+
+            KioskEvent::NavigateLightbox(index) => {
+                if self.state == KioskState::Lightbox {
+                    if let Some(ref session) = self.session {
+                        if index < session.photos.len() {
+                            self.lightbox_index = Some(index);
+                            commands.push(KioskCommand::UpdateUI);
+                        }
+                    }
+                }
+            }
+
             KioskEvent::ClearError => {
                 self.error = None;
                 commands.push(KioskCommand::UpdateUI);
             }
 
-            KioskEvent::WebSocketConnected | KioskEvent::WebSocketDisconnected => {
-                // These are informational, no state change needed
+            KioskEvent::WebSocketConnected => {
+                self.ws_reconnect_attempt = None;
+                commands.push(KioskCommand::UpdateUI);
+            }
+
+            KioskEvent::WebSocketDisconnected { attempt } => {
+                self.ws_reconnect_attempt = Some(attempt);
+                commands.push(KioskCommand::UpdateUI);
+            }
+
+            KioskEvent::PreviewConnectionFailed { error: _ } => {
+                // Informational - this doesn't block capture. `CameraFailed`/
+                // `CameraRecovered` still govern capture availability.
+            }
+
+            KioskEvent::CameraFailed => {
+                self.camera_available = false;
+                commands.push(KioskCommand::UpdateUI);
+            }
+
+            KioskEvent::CameraRecovered => {
+                self.camera_available = true;
+                commands.push(KioskCommand::UpdateUI);
+            }
+
+            KioskEvent::IdleTick => {
+                self.idle_ticks += 1;
+
+                match self.state {
+                    KioskState::Welcome if self.idle_ticks == config::ATTRACT_MODE_IDLE_TICKS => {
+                        self.attract_mode_active = true;
+                        commands.push(KioskCommand::StartAttractLoop);
+                        commands.push(KioskCommand::UpdateUI);
+                    }
+                    KioskState::Session
+                        if self.idle_ticks == config::SESSION_IDLE_TIMEOUT_TICKS =>
+                    {
+                        if let Some(ref session) = self.session {
+                            commands.push(KioskCommand::DisconnectWebSocket);
+                            commands.push(KioskCommand::DisconnectPreview);
+                            commands.push(KioskCommand::EndSession {
+                                session_id: session.id.clone(),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            KioskEvent::UserActivity => {
+                // Idle-timer reset and attract-mode exit are handled
+                // generically above, for any event other than `IdleTick`.
+            }
+
+            KioskEvent::StreamInfo {
+                width,
+                height,
+                codec,
+                bitrate,
+            } => {
+                self.stream_info = Some(StreamInfo {
+                    width,
+                    height,
+                    codec,
+                    bitrate,
+                });
+                commands.push(KioskCommand::UpdateUI);
             }
         }
 
@@ -281,6 +630,7 @@ mod tests {
         // Session created
         let cmds = sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
         assert_eq!(sm.state, KioskState::Session);
         assert!(!sm.is_loading);
@@ -297,6 +647,7 @@ mod tests {
         sm.process(KioskEvent::StartSession);
         sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
 
         // Trigger capture
@@ -316,6 +667,7 @@ mod tests {
                 id: "photo-1".into(),
                 thumbnail_url: "/thumb.jpg".into(),
                 web_url: "/photo.jpg".into(),
+                kind: MediaKind::Image,
             },
         });
         assert_eq!(sm.session.as_ref().unwrap().photos.len(), 1);
@@ -333,6 +685,7 @@ mod tests {
         sm.process(KioskEvent::StartSession);
         sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
 
         sm.process(KioskEvent::PhoneConnected);
@@ -351,6 +704,7 @@ mod tests {
         sm.process(KioskEvent::StartSession);
         sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
 
         // Add some photos
@@ -359,6 +713,7 @@ mod tests {
                 id: "photo-1".into(),
                 thumbnail_url: "/thumb1.jpg".into(),
                 web_url: "/photo1.jpg".into(),
+                kind: MediaKind::Image,
             },
         });
         sm.process(KioskEvent::PhotoReady {
@@ -366,6 +721,7 @@ mod tests {
                 id: "photo-2".into(),
                 thumbnail_url: "/thumb2.jpg".into(),
                 web_url: "/photo2.jpg".into(),
+                kind: MediaKind::Image,
             },
         });
 
@@ -388,12 +744,14 @@ mod tests {
         sm.process(KioskEvent::StartSession);
         sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
         sm.process(KioskEvent::PhotoReady {
             photo: PhotoInfo {
                 id: "photo-1".into(),
                 thumbnail_url: "/thumb.jpg".into(),
                 web_url: "/photo.jpg".into(),
+                kind: MediaKind::Image,
             },
         });
 
@@ -421,6 +779,7 @@ mod tests {
         sm.process(KioskEvent::StartSession);
         sm.process(KioskEvent::SessionCreated {
             id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
         });
 
         let cmds = sm.process(KioskEvent::EndSession);
@@ -433,4 +792,396 @@ mod tests {
         assert!(sm.session.is_none());
         assert!(sm.is_live_view());
     }
+
+    #[test]
+    fn test_preview_connected_on_session_created_and_disconnected_on_end() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+
+        let cmds = sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        assert!(cmds.iter().any(
+            |c| matches!(c, KioskCommand::ConnectPreview { session_id } if session_id == "test-123")
+        ));
+
+        let cmds = sm.process(KioskEvent::EndSession);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::DisconnectPreview)));
+    }
+
+    #[test]
+    fn test_capture_mode_threaded_into_trigger_capture() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+
+        sm.process(KioskEvent::SetCaptureMode(CaptureMode::Clip));
+        let cmds = sm.process(KioskEvent::TriggerCapture);
+        assert!(cmds.iter().any(
+            |c| matches!(c, KioskCommand::TriggerCapture { mode, .. } if *mode == CaptureMode::Clip)
+        ));
+    }
+
+    #[test]
+    fn test_clip_ready_stored_alongside_photos() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+
+        sm.process(KioskEvent::ClipReady {
+            clip: PhotoInfo {
+                id: "clip-1".into(),
+                thumbnail_url: "/thumb.jpg".into(),
+                web_url: "/clip.mp4".into(),
+                kind: MediaKind::Clip,
+            },
+        });
+        assert_eq!(sm.session.as_ref().unwrap().photos.len(), 1);
+        assert_eq!(sm.session.as_ref().unwrap().photos[0].kind, MediaKind::Clip);
+
+        // SelectPhoto works the same regardless of whether the item is a
+        // still photo or a clip
+        sm.process(KioskEvent::SelectPhoto(0));
+        assert_eq!(sm.viewing_photo, Some(0));
+    }
+
+    #[test]
+    fn test_join_url_exposed_on_session_created() {
+        let mut sm = KioskStateMachine::new();
+        assert_eq!(sm.join_url(), None);
+
+        sm.process(KioskEvent::StartSession);
+        let cmds = sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        assert_eq!(sm.join_url(), Some("https://host/join/test-123"));
+        assert!(cmds.iter().any(
+            |c| matches!(c, KioskCommand::RenderJoinQr { url } if url == "https://host/join/test-123")
+        ));
+
+        sm.process(KioskEvent::SessionEnded);
+        assert_eq!(sm.join_url(), None);
+    }
+
+    #[test]
+    fn test_camera_failure_blocks_capture() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        assert!(sm.camera_available);
+
+        sm.process(KioskEvent::CameraFailed);
+        assert!(!sm.camera_available);
+
+        // Try to capture while the camera is unavailable - should not emit command
+        let cmds = sm.process(KioskEvent::TriggerCapture);
+        assert!(!cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::TriggerCapture { .. })));
+
+        // Recover - capture should work again
+        sm.process(KioskEvent::CameraRecovered);
+        assert!(sm.camera_available);
+        let cmds = sm.process(KioskEvent::TriggerCapture);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::TriggerCapture { .. })));
+    }
+
+    #[test]
+    fn test_websocket_reconnect_attempt_tracked() {
+        let mut sm = KioskStateMachine::new();
+        assert_eq!(sm.ws_reconnect_attempt, None);
+
+        sm.process(KioskEvent::WebSocketDisconnected { attempt: 1 });
+        assert_eq!(sm.ws_reconnect_attempt, Some(1));
+
+        sm.process(KioskEvent::WebSocketDisconnected { attempt: 2 });
+        assert_eq!(sm.ws_reconnect_attempt, Some(2));
+
+        sm.process(KioskEvent::WebSocketConnected);
+        assert_eq!(sm.ws_reconnect_attempt, None);
+    }
+
+    #[test]
+    fn test_stream_info_stored_on_session_created() {
+        let mut sm = KioskStateMachine::new();
+        assert!(sm.stream_info.is_none());
+
+        sm.process(KioskEvent::StartSession);
+        let cmds = sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::ReportStreamInfo)));
+
+        sm.process(KioskEvent::StreamInfo {
+            width: 1280,
+            height: 720,
+            codec: "H.264".into(),
+            bitrate: 2_000_000,
+        });
+        let info = sm.stream_info.as_ref().unwrap();
+        assert_eq!(info.width, 1280);
+        assert_eq!(info.codec, "H.264");
+    }
+
+    #[test]
+    fn test_attract_mode_after_idle_on_welcome() {
+        let mut sm = KioskStateMachine::new();
+        assert!(!sm.attract_mode_active);
+
+        for _ in 0..config::ATTRACT_MODE_IDLE_TICKS - 1 {
+            sm.process(KioskEvent::IdleTick);
+        }
+        assert!(!sm.attract_mode_active);
+
+        let cmds = sm.process(KioskEvent::IdleTick);
+        assert!(sm.attract_mode_active);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::StartAttractLoop)));
+    }
+
+    #[test]
+    fn test_user_activity_cancels_attract_mode() {
+        let mut sm = KioskStateMachine::new();
+        for _ in 0..config::ATTRACT_MODE_IDLE_TICKS {
+            sm.process(KioskEvent::IdleTick);
+        }
+        assert!(sm.attract_mode_active);
+
+        let cmds = sm.process(KioskEvent::UserActivity);
+        assert!(!sm.attract_mode_active);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::StopAttractLoop)));
+    }
+
+    #[test]
+    fn test_idle_session_auto_ends() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+
+        for _ in 0..config::SESSION_IDLE_TIMEOUT_TICKS - 1 {
+            sm.process(KioskEvent::IdleTick);
+        }
+        assert!(sm.session.is_some());
+
+        let cmds = sm.process(KioskEvent::IdleTick);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::EndSession { .. })));
+    }
+
+    #[test]
+    fn test_session_end_populates_recent_photos_and_trims_overflow() {
+        let mut sm = KioskStateMachine::new();
+        assert!(sm.recent_photos.is_empty());
+
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        for i in 0..config::ATTRACT_PHOTO_HISTORY_LIMIT + 2 {
+            sm.process(KioskEvent::PhotoReady {
+                photo: PhotoInfo {
+                    id: format!("photo-{i}"),
+                    thumbnail_url: format!("/thumb-{i}.jpg"),
+                    web_url: format!("/photo-{i}.jpg"),
+                    kind: MediaKind::Image,
+                },
+            });
+        }
+        sm.process(KioskEvent::EndSession);
+        sm.process(KioskEvent::SessionEnded);
+
+        assert_eq!(sm.recent_photos.len(), config::ATTRACT_PHOTO_HISTORY_LIMIT);
+        // Oldest photos were dropped, so the history starts at photo-2
+        assert_eq!(sm.recent_photos.first().unwrap().id, "photo-2");
+        assert_eq!(
+            sm.recent_photos.last().unwrap().id,
+            format!("photo-{}", config::ATTRACT_PHOTO_HISTORY_LIMIT + 1)
+        );
+    }
+
+    /// Start a session and seed it with one photo, `photo-1`, for the
+    /// photo-action tests below.
+    fn session_with_one_photo() -> KioskStateMachine {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        sm.process(KioskEvent::PhotoReady {
+            photo: PhotoInfo {
+                id: "photo-1".into(),
+                thumbnail_url: "/thumb-1.jpg".into(),
+                web_url: "/photo-1.jpg".into(),
+                kind: MediaKind::Image,
+            },
+        });
+        sm
+    }
+
+    #[test]
+    fn test_delete_photo_flow() {
+        let mut sm = session_with_one_photo();
+
+        let cmds = sm.process(KioskEvent::DeletePhoto {
+            photo_id: "photo-1".into(),
+        });
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::DeletePhoto { photo_id, .. } if photo_id == "photo-1")));
+
+        sm.process(KioskEvent::PhotoDeleted {
+            photo_id: "photo-1".into(),
+        });
+        assert!(sm.session.as_ref().unwrap().photos.is_empty());
+    }
+
+    #[test]
+    fn test_mark_photo_for_print_flow() {
+        let mut sm = session_with_one_photo();
+
+        let cmds = sm.process(KioskEvent::MarkPhotoForPrint {
+            photo_id: "photo-1".into(),
+        });
+        assert!(cmds.iter().any(
+            |c| matches!(c, KioskCommand::MarkPhotoForPrint { photo_id, .. } if photo_id == "photo-1")
+        ));
+    }
+
+    #[test]
+    fn test_photo_action_failed_surfaces_error() {
+        let mut sm = session_with_one_photo();
+
+        let cmds = sm.process(KioskEvent::PhotoActionFailed {
+            error: "server error".into(),
+        });
+        assert_eq!(sm.error.as_deref(), Some("server error"));
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::ScheduleErrorClear)));
+    }
+
+    #[test]
+    fn test_upload_edited_photo_flow_replaces_photo_in_place() {
+        let mut sm = session_with_one_photo();
+
+        let cmds = sm.process(KioskEvent::UploadEditedPhoto {
+            photo_id: "photo-1".into(),
+            bytes: vec![1, 2, 3],
+        });
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::UploadEditedPhoto { photo_id, .. } if photo_id == "photo-1")));
+
+        sm.process(KioskEvent::PhotoUpdated {
+            photo: PhotoInfo {
+                id: "photo-1".into(),
+                thumbnail_url: "/thumb-1-edited.jpg".into(),
+                web_url: "/photo-1-edited.jpg".into(),
+                kind: MediaKind::Image,
+            },
+        });
+        let photos = &sm.session.as_ref().unwrap().photos;
+        assert_eq!(photos.len(), 1);
+        assert_eq!(photos[0].web_url, "/photo-1-edited.jpg");
+    }
+
+    #[test]
+    fn test_trigger_capture_enters_capturing_until_countdown_starts() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+
+        let cmds = sm.process(KioskEvent::TriggerCapture);
+        assert_eq!(sm.state, KioskState::Capturing);
+        assert!(cmds
+            .iter()
+            .any(|c| matches!(c, KioskCommand::TriggerCapture { .. })));
+
+        sm.process(KioskEvent::CountdownTick { value: 3 });
+        assert_eq!(sm.state, KioskState::Countdown);
+    }
+
+    #[test]
+    fn test_capture_failed_exits_capturing() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::StartSession);
+        sm.process(KioskEvent::SessionCreated {
+            id: "test-123".into(),
+            join_url: "https://host/join/test-123".into(),
+        });
+        sm.process(KioskEvent::TriggerCapture);
+        assert_eq!(sm.state, KioskState::Capturing);
+
+        sm.process(KioskEvent::CaptureFailed {
+            error: "camera error".into(),
+        });
+        assert_eq!(sm.state, KioskState::Session);
+    }
+
+    #[test]
+    fn test_lightbox_open_navigate_close() {
+        let mut sm = session_with_one_photo();
+        sm.process(KioskEvent::PhotoReady {
+            photo: PhotoInfo {
+                id: "photo-2".into(),
+                thumbnail_url: "/thumb-2.jpg".into(),
+                web_url: "/photo-2.jpg".into(),
+                kind: MediaKind::Image,
+            },
+        });
+
+        let cmds = sm.process(KioskEvent::OpenLightbox(0));
+        assert_eq!(sm.state, KioskState::Lightbox);
+        assert_eq!(sm.lightbox_index, Some(0));
+        assert!(cmds.iter().any(|c| matches!(c, KioskCommand::UpdateUI)));
+
+        sm.process(KioskEvent::NavigateLightbox(1));
+        assert_eq!(sm.lightbox_index, Some(1));
+
+        // Out-of-range navigation is ignored
+        sm.process(KioskEvent::NavigateLightbox(99));
+        assert_eq!(sm.lightbox_index, Some(1));
+
+        sm.process(KioskEvent::CloseLightbox);
+        assert_eq!(sm.state, KioskState::Session);
+        assert!(sm.lightbox_index.is_none());
+    }
+
+    #[test]
+    fn test_open_lightbox_ignored_outside_session() {
+        let mut sm = KioskStateMachine::new();
+        sm.process(KioskEvent::OpenLightbox(0));
+        assert_eq!(sm.state, KioskState::Welcome);
+        assert!(sm.lightbox_index.is_none());
+    }
 }
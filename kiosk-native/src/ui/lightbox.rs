@@ -1,21 +1,129 @@
-//! Full-screen photo lightbox viewer.
+//! Full-screen photo/clip lightbox viewer.
 
 use gtk4 as gtk;
 use gtk4::prelude::*;
+use std::cell::Cell;
+use std::path::Path;
 use std::rc::Rc;
 
-use crate::api::PhotoInfo;
+use crate::api::{MediaKind, PhotoInfo};
 use crate::app::AppContext;
 use crate::config;
+use crate::ui::widgets::{animations, ScaleBin};
 
-/// Create the lightbox overlay
+/// CSS class on the stack that swaps between the still-image `Picture` and
+/// the clip `Video` widget, so `update_lightbox` can find it again.
+const MEDIA_STACK_CLASS: &str = "lightbox-media-stack";
+
+/// Swipe velocity (px/s) past which a flick counts as a navigate/close
+/// gesture rather than an incidental touch drag.
+const SWIPE_VELOCITY_THRESHOLD: f64 = 300.0;
+
+/// Duration of the open/close scale-reveal transition.
+const SCALE_REVEAL_DURATION_MS: u32 = 250;
+
+/// Lower bound of the pinch/double-tap zoom range - "fit" (the reveal's
+/// resting scale).
+const MIN_ZOOM_SCALE: f64 = 1.0;
+
+/// Upper bound of the pinch zoom range.
+const MAX_ZOOM_SCALE: f64 = 4.0;
+
+/// Scale a double-tap zooms in to.
+const DOUBLE_TAP_ZOOM_SCALE: f64 = 2.0;
+
+/// Slop above `MIN_ZOOM_SCALE` before the image counts as "zoomed in" -
+/// beyond this, drags pan instead of navigating and double-tap zooms back
+/// out instead of further in.
+const ZOOM_EPSILON: f64 = 0.01;
+
+/// Guard shared between `create_lightbox` and every subsequent
+/// `update_lightbox` call for the same widget, so an in-flight fetch can
+/// tell whether its result is still wanted by the time it completes - either
+/// because the lightbox was torn down, or because the user swiped past the
+/// index it was loading for before the fetch caught up.
+#[derive(Clone)]
+pub struct LightboxGuard {
+    /// Dropped to `false` once the lightbox is destroyed
+    alive: Rc<Cell<bool>>,
+    /// The index the lightbox is currently showing (or navigating to),
+    /// updated by `update_lightbox` before it kicks off the new fetch
+    current_index: Rc<Cell<usize>>,
+}
+
+impl LightboxGuard {
+    fn new(initial_index: usize) -> Self {
+        Self {
+            alive: Rc::new(Cell::new(true)),
+            current_index: Rc::new(Cell::new(initial_index)),
+        }
+    }
+
+    /// Whether a fetch kicked off for `index` is still the one the lightbox
+    /// wants - false once destroyed, or once navigation has moved on
+    fn is_current(&self, index: usize) -> bool {
+        self.alive.get() && self.current_index.get() == index
+    }
+}
+
+/// Work out the (scale, translate_x, translate_y) that would make `bin`
+/// exactly cover `bounds` (a widget's on-screen rect, e.g. a tapped
+/// thumbnail's `compute_bounds` relative to the same ancestor the lightbox
+/// is added to) instead of its own fully-expanded allocation.
+fn compute_reveal_transform(bin: &ScaleBin, bounds: &gtk::graphene::Rect) -> (f64, f64, f64) {
+    let width = bin.width() as f64;
+    let height = bin.height() as f64;
+    if width <= 0.0 || height <= 0.0 {
+        return (1.0, 0.0, 0.0);
+    }
+
+    let scale = (bounds.width() as f64 / width)
+        .max(bounds.height() as f64 / height)
+        .clamp(0.05, 1.0);
+    let tx = (bounds.x() as f64 + bounds.width() as f64 / 2.0) - width / 2.0;
+    let ty = (bounds.y() as f64 + bounds.height() as f64 / 2.0) - height / 2.0;
+
+    (scale, tx, ty)
+}
+
+/// Shrink `bin` back down into `source_bounds` (if known) before calling
+/// `on_close`, mirroring the open reveal in reverse; with no source bounds
+/// this just calls `on_close` directly and the stack's own crossfade
+/// transition takes over.
+fn close_with_reveal(
+    bin: &ScaleBin,
+    source_bounds: Option<gtk::graphene::Rect>,
+    on_close: impl Fn() + 'static,
+) {
+    match source_bounds {
+        Some(bounds) => {
+            let target = compute_reveal_transform(bin, &bounds);
+            animations::scale_reveal(
+                bin,
+                (1.0, 0.0, 0.0),
+                target,
+                SCALE_REVEAL_DURATION_MS,
+                Some(Box::new(on_close)),
+            );
+        }
+        None => on_close(),
+    }
+}
+
+/// Create the lightbox overlay.
+///
+/// `source_bounds` is the tapped thumbnail's rect (e.g. from
+/// `thumbnail.compute_bounds(&main_window.stack)`), used to scale-reveal the
+/// lightbox open from the thumbnail's position and reverse the animation on
+/// close; pass `None` to just use the surrounding stack's crossfade.
 pub fn create_lightbox(
     ctx: &Rc<AppContext>,
     photos: &[PhotoInfo],
     initial_index: usize,
+    source_bounds: Option<gtk::graphene::Rect>,
     on_close: impl Fn() + Clone + 'static,
     on_navigate: impl Fn(usize) + Clone + 'static,
-) -> gtk::Box {
+) -> (gtk::Box, LightboxGuard) {
     let lightbox = gtk::Box::new(gtk::Orientation::Vertical, 0);
     lightbox.add_css_class("lightbox");
     lightbox.set_hexpand(true);
@@ -40,10 +148,8 @@ pub fn create_lightbox(
     spacer.set_hexpand(true);
 
     // Close button
-    let on_close_clone = on_close.clone();
     let close_button = gtk::Button::with_label("\u{2715}");
     close_button.add_css_class("lightbox-close");
-    close_button.connect_clicked(move |_| on_close_clone());
 
     top_bar.append(&counter);
     top_bar.append(&spacer);
@@ -60,18 +166,118 @@ pub fn create_lightbox(
     prev_button.set_valign(gtk::Align::Center);
     prev_button.set_sensitive(initial_index > 0);
 
-    // Image
+    // Still-image widget
     let picture = gtk::Picture::new();
     picture.set_content_fit(gtk::ContentFit::Contain);
     picture.set_hexpand(true);
     picture.set_vexpand(true);
     picture.add_css_class("lightbox-image");
 
-    // Load initial image
+    // Clip-playback widget: `gtk::Video` ships play/pause/seek controls for free
+    let video = gtk::Video::new();
+    video.set_hexpand(true);
+    video.set_vexpand(true);
+    video.set_autoplay(true);
+    video.set_loop(true);
+    video.add_css_class("lightbox-video");
+
+    // Stack swaps between the two so only one widget type is ever present,
+    // mirroring how a still image and a video are interchangeable in the
+    // same viewer surface
+    let media_stack = gtk::Stack::new();
+    media_stack.add_css_class(MEDIA_STACK_CLASS);
+    media_stack.set_hexpand(true);
+    media_stack.set_vexpand(true);
+    media_stack.add_named(&picture, Some("image"));
+    media_stack.add_named(&video, Some("video"));
+
+    // Wrapped in a ScaleBin so the open/close reveal can scale+translate the
+    // media at paint time without relayouting `image_area`
+    let media_bin = ScaleBin::new(&media_stack);
+    media_bin.set_hexpand(true);
+    media_bin.set_vexpand(true);
+
+    let guard = LightboxGuard::new(initial_index);
+
+    // Load initial media
     if let Some(photo) = photos.get(initial_index) {
-        load_lightbox_image(ctx, &photo.web_url, &picture);
+        load_lightbox_media(
+            ctx,
+            photo,
+            &media_stack,
+            &picture,
+            &video,
+            initial_index,
+            guard.clone(),
+        );
+    }
+    preload_adjacent(ctx, photos, initial_index, &guard);
+
+    // Scale-reveal open: once the bin has its fullscreen allocation, animate
+    // it in from the tapped thumbnail's bounds
+    if let Some(bounds) = source_bounds.clone() {
+        media_bin.connect_map(move |bin| {
+            let from = compute_reveal_transform(bin, &bounds);
+            animations::scale_reveal(bin, from, (1.0, 0.0, 0.0), SCALE_REVEAL_DURATION_MS, None);
+        });
     }
 
+    // Pinch-to-zoom and pan: the `ScaleBin`'s own scale/translate are the
+    // single source of truth for "current zoom", so gestures just read and
+    // write them directly instead of tracking parallel state.
+    let zoom_gesture = gtk::GestureZoom::new();
+    let zoom_base_scale = Rc::new(Cell::new(MIN_ZOOM_SCALE));
+    let bin_zoom_begin = media_bin.clone();
+    let zoom_base_scale_begin = zoom_base_scale.clone();
+    zoom_gesture.connect_begin(move |_, _| {
+        zoom_base_scale_begin.set(bin_zoom_begin.scale());
+    });
+    let bin_zoom = media_bin.clone();
+    zoom_gesture.connect_scale_changed(move |_, scale| {
+        let new_scale = (zoom_base_scale.get() * scale).clamp(MIN_ZOOM_SCALE, MAX_ZOOM_SCALE);
+        bin_zoom.set_scale(new_scale);
+    });
+    media_bin.add_controller(zoom_gesture);
+
+    let drag_gesture = gtk::GestureDrag::new();
+    let drag_base_offset = Rc::new(Cell::new((0.0_f64, 0.0_f64)));
+    let bin_drag_begin = media_bin.clone();
+    let drag_base_offset_begin = drag_base_offset.clone();
+    drag_gesture.connect_drag_begin(move |_, _, _| {
+        drag_base_offset_begin.set(bin_drag_begin.translate());
+    });
+    let bin_drag = media_bin.clone();
+    drag_gesture.connect_drag_update(move |_, dx, dy| {
+        if bin_drag.scale() > MIN_ZOOM_SCALE + ZOOM_EPSILON {
+            let (base_x, base_y) = drag_base_offset.get();
+            bin_drag.set_translate(base_x + dx, base_y + dy);
+        }
+    });
+    media_bin.add_controller(drag_gesture);
+
+    // Double-tap toggles between fit and a 2x zoom centered on the tap point
+    let double_tap_gesture = gtk::GestureClick::new();
+    let bin_double_tap = media_bin.clone();
+    double_tap_gesture.connect_pressed(move |gesture, n_press, x, y| {
+        if n_press != 2 {
+            return;
+        }
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+
+        if bin_double_tap.scale() > MIN_ZOOM_SCALE + ZOOM_EPSILON {
+            bin_double_tap.set_scale(MIN_ZOOM_SCALE);
+            bin_double_tap.set_translate(0.0, 0.0);
+        } else {
+            let center_x = bin_double_tap.width() as f64 / 2.0;
+            let center_y = bin_double_tap.height() as f64 / 2.0;
+            let tx = (x - center_x) * (1.0 - DOUBLE_TAP_ZOOM_SCALE);
+            let ty = (y - center_y) * (1.0 - DOUBLE_TAP_ZOOM_SCALE);
+            bin_double_tap.set_scale(DOUBLE_TAP_ZOOM_SCALE);
+            bin_double_tap.set_translate(tx, ty);
+        }
+    });
+    media_bin.add_controller(double_tap_gesture);
+
     // Next button
     let next_button = gtk::Button::with_label("\u{203A}");
     next_button.add_css_class("lightbox-nav");
@@ -79,25 +285,42 @@ pub fn create_lightbox(
     next_button.set_valign(gtk::Align::Center);
     next_button.set_sensitive(initial_index < photos.len().saturating_sub(1));
 
-    // Navigation callbacks
+    // Pause any playing clip before leaving the item it belongs to, so only
+    // the visible clip ever plays
+    let video_on_close = video.clone();
+    let bin_on_close = media_bin.clone();
+    let on_close_clone = on_close.clone();
+    let source_bounds_close = source_bounds.clone();
+    close_button.connect_clicked(move |_| {
+        video_on_close.pause();
+        let on_close_clone = on_close_clone.clone();
+        close_with_reveal(&bin_on_close, source_bounds_close.clone(), move || {
+            on_close_clone()
+        });
+    });
+
     let on_navigate_prev = on_navigate.clone();
     let current_idx = initial_index;
+    let video_prev = video.clone();
     prev_button.connect_clicked(move |_| {
         if current_idx > 0 {
+            video_prev.pause();
             on_navigate_prev(current_idx - 1);
         }
     });
 
     let on_navigate_next = on_navigate.clone();
     let photo_count = photos.len();
+    let video_next = video.clone();
     next_button.connect_clicked(move |_| {
         if current_idx < photo_count.saturating_sub(1) {
+            video_next.pause();
             on_navigate_next(current_idx + 1);
         }
     });
 
     image_area.append(&prev_button);
-    image_area.append(&picture);
+    image_area.append(&media_bin);
     image_area.append(&next_button);
 
     lightbox.append(&top_bar);
@@ -107,19 +330,26 @@ pub fn create_lightbox(
     let key_controller = gtk::EventControllerKey::new();
     let on_close_key = on_close.clone();
     let on_nav_key = on_navigate.clone();
+    let video_key = video.clone();
+    let bin_key = media_bin.clone();
+    let source_bounds_key = source_bounds.clone();
     let idx = initial_index;
     let count = photos.len();
     key_controller.connect_key_pressed(move |_, key, _, _| {
         match key {
             gtk::gdk::Key::Escape => {
-                on_close_key();
+                video_key.pause();
+                let on_close_key = on_close_key.clone();
+                close_with_reveal(&bin_key, source_bounds_key.clone(), move || on_close_key());
                 glib::Propagation::Stop
             }
             gtk::gdk::Key::Left if idx > 0 => {
+                video_key.pause();
                 on_nav_key(idx - 1);
                 glib::Propagation::Stop
             }
             gtk::gdk::Key::Right if idx < count.saturating_sub(1) => {
+                video_key.pause();
                 on_nav_key(idx + 1);
                 glib::Propagation::Stop
             }
@@ -128,21 +358,111 @@ pub fn create_lightbox(
     });
     lightbox.add_controller(key_controller);
 
-    lightbox
+    // Touch swipe: left/right flicks navigate, a downward flick closes -
+    // the natural gesture set on a touchscreen kiosk that otherwise only
+    // offers on-screen arrow buttons
+    let swipe_gesture = gtk::GestureSwipe::new();
+    let on_close_swipe = on_close.clone();
+    let on_nav_swipe = on_navigate.clone();
+    let video_swipe = video.clone();
+    let bin_swipe = media_bin.clone();
+    let source_bounds_swipe = source_bounds.clone();
+    let idx_swipe = initial_index;
+    let count_swipe = photos.len();
+    swipe_gesture.connect_swipe(move |_, vx, vy| {
+        // While zoomed in, a drag on the image pans instead of navigating
+        if bin_swipe.scale() > MIN_ZOOM_SCALE + ZOOM_EPSILON {
+            return;
+        }
+        if vy.abs() > vx.abs() && vy > SWIPE_VELOCITY_THRESHOLD {
+            video_swipe.pause();
+            let on_close_swipe = on_close_swipe.clone();
+            close_with_reveal(&bin_swipe, source_bounds_swipe.clone(), move || {
+                on_close_swipe()
+            });
+        } else if vx.abs() > SWIPE_VELOCITY_THRESHOLD {
+            if vx < 0.0 && idx_swipe < count_swipe.saturating_sub(1) {
+                video_swipe.pause();
+                on_nav_swipe(idx_swipe + 1);
+            } else if vx > 0.0 && idx_swipe > 0 {
+                video_swipe.pause();
+                on_nav_swipe(idx_swipe - 1);
+            }
+        }
+    });
+    lightbox.add_controller(swipe_gesture);
+
+    // Once the lightbox widget is torn down (the screen stack removes it on
+    // close/navigation away), mark in-flight fetches stale and drop the
+    // cached textures - they're decoded bitmaps and not worth holding onto
+    // once nothing is showing them, especially on memory-limited kiosk
+    // hardware.
+    let ctx_destroy = ctx.clone();
+    let guard_destroy = guard.clone();
+    lightbox.connect_destroy(move |_| {
+        guard_destroy.alive.set(false);
+        ctx_destroy.texture_cache.clear();
+    });
+
+    (lightbox, guard)
+}
+
+/// Load a photo or clip into the lightbox, swapping the media stack to the
+/// widget that matches its kind
+fn load_lightbox_media(
+    ctx: &Rc<AppContext>,
+    photo: &PhotoInfo,
+    media_stack: &gtk::Stack,
+    picture: &gtk::Picture,
+    video: &gtk::Video,
+    index: usize,
+    guard: LightboxGuard,
+) {
+    match photo.kind {
+        MediaKind::Image => {
+            video.pause();
+            video.set_media_stream(None::<&gtk::MediaFile>);
+            media_stack.set_visible_child_name("image");
+            load_lightbox_image(ctx, &photo.web_url, picture, index, guard);
+        }
+        MediaKind::Clip => {
+            media_stack.set_visible_child_name("video");
+            load_lightbox_clip(ctx, &photo.id, &photo.web_url, video, index, guard);
+        }
+    }
 }
 
-/// Load an image into the lightbox picture widget
-fn load_lightbox_image(ctx: &Rc<AppContext>, url: &str, picture: &gtk::Picture) {
+/// Load an image into the lightbox picture widget, consulting the shared
+/// texture cache before fetching it over the network.
+fn load_lightbox_image(
+    ctx: &Rc<AppContext>,
+    url: &str,
+    picture: &gtk::Picture,
+    index: usize,
+    guard: LightboxGuard,
+) {
     let full_url = config::photo_url(url);
+
+    if let Some(texture) = ctx.texture_cache.get(&full_url) {
+        picture.set_paintable(Some(&texture));
+        return;
+    }
+
     let picture = picture.clone();
     let api = ctx.api.clone();
     let runtime = ctx.runtime.clone();
+    let ctx = ctx.clone();
+    let cache_url = full_url.clone();
 
     glib::spawn_future_local(async move {
         let result = runtime.spawn(async move {
             api.fetch_image(&full_url).await
         }).await;
 
+        if !guard.is_current(index) {
+            return;
+        }
+
         match result {
             Ok(Ok(bytes)) => {
                 let gbytes = glib::Bytes::from(&bytes);
@@ -152,6 +472,7 @@ fn load_lightbox_image(ctx: &Rc<AppContext>, url: &str, picture: &gtk::Picture)
                     None::<&gtk::gio::Cancellable>,
                 ) {
                     let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
+                    ctx.texture_cache.insert(cache_url, texture.clone());
                     picture.set_paintable(Some(&texture));
                 }
             }
@@ -165,13 +486,132 @@ fn load_lightbox_image(ctx: &Rc<AppContext>, url: &str, picture: &gtk::Picture)
     });
 }
 
-/// Update lightbox to show a different photo
+/// Fetch and decode an image into the texture cache without touching any
+/// picture widget, so flicking to `index ± 1` feels instant once the visible
+/// photo itself catches up.
+fn preload_image(ctx: &Rc<AppContext>, url: &str, guard: LightboxGuard) {
+    let full_url = config::photo_url(url);
+
+    // Already cached (this also refreshes its LRU position) - nothing to do
+    if ctx.texture_cache.get(&full_url).is_some() {
+        return;
+    }
+
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+    let ctx = ctx.clone();
+    let cache_url = full_url.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime.spawn(async move {
+            api.fetch_image(&full_url).await
+        }).await;
+
+        if !guard.alive.get() {
+            return;
+        }
+
+        if let Ok(Ok(bytes)) = result {
+            let gbytes = glib::Bytes::from(&bytes);
+            let stream = gtk::gio::MemoryInputStream::from_bytes(&gbytes);
+            if let Ok(pixbuf) =
+                gtk::gdk_pixbuf::Pixbuf::from_stream(&stream, None::<&gtk::gio::Cancellable>)
+            {
+                let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
+                ctx.texture_cache.insert(cache_url, texture);
+            }
+        }
+    });
+}
+
+/// Preload the still images immediately before and after `index`, if any -
+/// clips aren't texture-cached so they're left for the on-demand fetch in
+/// `load_lightbox_clip`.
+fn preload_adjacent(
+    ctx: &Rc<AppContext>,
+    photos: &[PhotoInfo],
+    index: usize,
+    guard: &LightboxGuard,
+) {
+    let neighbors = [
+        index.checked_sub(1),
+        index.checked_add(1).filter(|&i| i < photos.len()),
+    ];
+
+    for neighbor in neighbors.into_iter().flatten() {
+        if let Some(photo) = photos.get(neighbor) {
+            if photo.kind == MediaKind::Image {
+                preload_image(ctx, &photo.web_url, guard.clone());
+            }
+        }
+    }
+}
+
+/// Load a boomerang clip into the lightbox video widget.
+///
+/// `gtk::MediaFile::for_input_stream` needs a seekable source, which the
+/// fetched bytes aren't, so they're written to a temp file and opened from
+/// there instead - the same trick `MediaFile::for_filename` is built for.
+fn load_lightbox_clip(
+    ctx: &Rc<AppContext>,
+    clip_id: &str,
+    url: &str,
+    video: &gtk::Video,
+    index: usize,
+    guard: LightboxGuard,
+) {
+    let full_url = config::photo_url(url);
+    let ext = Path::new(url)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_string();
+    let temp_path = std::env::temp_dir().join(format!("picpop-clip-{}.{}", clip_id, ext));
+    let video = video.clone();
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime.spawn(async move {
+            api.fetch_image(&full_url).await
+        }).await;
+
+        match result {
+            Ok(Ok(bytes)) => {
+                if let Err(e) = std::fs::write(&temp_path, &bytes) {
+                    log::error!("Failed to write clip to temp file: {}", e);
+                    return;
+                }
+                if !guard.is_current(index) {
+                    return;
+                }
+                let media_file = gtk::MediaFile::for_filename(&temp_path);
+                media_file.set_loop(true);
+                video.set_media_stream(Some(&media_file));
+                media_file.play();
+            }
+            Ok(Err(e)) => {
+                log::error!("Failed to load lightbox clip: {}", e);
+            }
+            Err(e) => {
+                log::error!("Task join error: {}", e);
+            }
+        }
+    });
+}
+
+/// Update lightbox to show a different photo or clip
 pub fn update_lightbox(
     ctx: &Rc<AppContext>,
     lightbox: &gtk::Box,
     photos: &[PhotoInfo],
     index: usize,
+    guard: &LightboxGuard,
 ) {
+    // Moved before the fetch below so a previous index's in-flight fetch
+    // reads the new value and treats itself as stale as soon as it completes
+    guard.current_index.set(index);
+
     // Find and update the counter
     if let Some(top_bar) = lightbox.first_child() {
         if let Some(bar) = top_bar.downcast_ref::<gtk::Box>() {
@@ -183,19 +623,54 @@ pub fn update_lightbox(
         }
     }
 
-    // Find and update the image
+    // Find and update the media
     if let Some(child) = lightbox.first_child() {
         let mut sibling = child.next_sibling();
         while let Some(widget) = sibling {
             if let Some(image_area) = widget.downcast_ref::<gtk::Box>() {
-                // Find the picture in the image area
+                // Find the media stack in the image area
                 let mut img_child = image_area.first_child();
                 while let Some(img_widget) = img_child {
-                    if let Some(picture) = img_widget.downcast_ref::<gtk::Picture>() {
-                        if let Some(photo) = photos.get(index) {
-                            load_lightbox_image(ctx, &photo.web_url, picture);
+                    // The media stack lives inside the reveal-animation `ScaleBin`,
+                    // which also doubles as the pinch-zoom/pan transform - reset it
+                    // so every newly-loaded photo starts back at fit
+                    if let Some(bin) = img_widget.downcast_ref::<ScaleBin>() {
+                        bin.set_scale(MIN_ZOOM_SCALE);
+                        bin.set_translate(0.0, 0.0);
+                    }
+                    if let Some(media_stack) = img_widget
+                        .downcast_ref::<ScaleBin>()
+                        .and_then(|bin| bin.first_child())
+                        .and_then(|w| w.downcast::<gtk::Stack>().ok())
+                    {
+                        if media_stack
+                            .css_classes()
+                            .iter()
+                            .any(|c| c == MEDIA_STACK_CLASS)
+                        {
+                            if let (Some(photo), Some(picture), Some(video)) = (
+                                photos.get(index),
+                                media_stack
+                                    .child_by_name("image")
+                                    .and_then(|w| w.downcast::<gtk::Picture>().ok()),
+                                media_stack
+                                    .child_by_name("video")
+                                    .and_then(|w| w.downcast::<gtk::Video>().ok()),
+                            ) {
+                                // Stop whatever clip was playing before swapping content
+                                video.pause();
+                                load_lightbox_media(
+                                    ctx,
+                                    photo,
+                                    &media_stack,
+                                    &picture,
+                                    &video,
+                                    index,
+                                    guard.clone(),
+                                );
+                                preload_adjacent(ctx, photos, index, guard);
+                            }
                         }
-                        break;
                     }
                     // Update nav button sensitivity
                     if let Some(button) = img_widget.downcast_ref::<gtk::Button>() {
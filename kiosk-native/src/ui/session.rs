@@ -5,14 +5,68 @@ use gtk4 as gtk;
 use gtk4::prelude::*;
 use libadwaita as adw;
 use libadwaita::prelude::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::Path;
 use std::rc::Rc;
 
-use crate::api::PhotoInfo;
+use futures_util::future::{abortable, AbortHandle};
+
+use crate::api::{MediaKind, PhotoInfo};
 use crate::app::AppContext;
 use crate::config;
-use crate::ui::widgets::{self, animations, qr_image::ExpandableQrPanel};
+use crate::state::StreamInfo;
+use crate::ui::widgets::{
+    self, animations, qr_image::ExpandableQrPanel, CaptureRingIndicator, DiagnosticsOverlay,
+    PhotoEditor, ScaleBin,
+};
+
+/// Swipe velocity (px/s) past which a flick in the media viewer counts as a
+/// navigate/close gesture rather than an incidental touch drag.
+const VIEWER_SWIPE_VELOCITY_THRESHOLD: f64 = 300.0;
+
+/// Lower bound of the media viewer's pinch zoom range ("fit").
+const VIEWER_MIN_ZOOM_SCALE: f64 = 1.0;
+
+/// Upper bound of the media viewer's pinch zoom range.
+const VIEWER_MAX_ZOOM_SCALE: f64 = 4.0;
+
+/// Slop above `VIEWER_MIN_ZOOM_SCALE` before the viewer counts as "zoomed
+/// in" - beyond this, drags pan instead of navigating/closing.
+const VIEWER_ZOOM_EPSILON: f64 = 0.01;
+
+/// Margin (in pixels) beyond the photo strip's visible edges within which an
+/// as-yet-unloaded thumbnail still starts fetching, so the user doesn't see
+/// a blank tile for the last few pixels of a scroll.
+const STRIP_PREFETCH_MARGIN_PX: f32 = 200.0;
+
+/// An action triggered from a strip thumbnail's long-press/secondary-click
+/// context menu, forwarded verbatim to whoever registers `connect_photo_action`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoAction {
+    /// Pin this photo to the front of the strip
+    Pin,
+    /// Unpin a previously-pinned photo
+    Unpin,
+    /// Delete this photo from the session
+    Delete,
+    /// Mark this photo for printing
+    MarkForPrint,
+    /// Open this photo in the full-screen photo editor
+    Edit,
+}
 
+/// Widgets backing the fullscreen `MediaViewer` overlay, shown when a strip
+/// thumbnail is tapped. All fields are GObject handles, so cloning just
+/// shares the underlying widgets rather than duplicating them.
+#[derive(Clone)]
+struct MediaViewerWidgets {
+    overlay: gtk::Box,
+    bin: ScaleBin,
+    media_stack: gtk::Stack,
+    picture: gtk::Picture,
+    video: gtk::Video,
+}
 
 /// References to updateable widgets in the session screen
 #[allow(dead_code)]
@@ -33,8 +87,40 @@ pub struct SessionWidgets {
     loaded_photos: Rc<RefCell<Vec<String>>>,
     /// Current selection state
     current_selection: Rc<RefCell<Option<usize>>>,
+    /// The full photo list from the most recent `update_photos` call, so the
+    /// media viewer can navigate between entries without round-tripping
+    /// through the caller
+    current_photos: Rc<RefCell<Vec<PhotoInfo>>>,
+    /// Fullscreen media viewer overlay
+    media_viewer: MediaViewerWidgets,
+    /// Full-screen crop/rotate/brightness editor, launched from the media
+    /// viewer's edit button or a strip thumbnail's context menu
+    photo_editor: Rc<PhotoEditor>,
+    /// Muted looping clip previews currently in the grid, so
+    /// `update_clip_visibility` can play/pause them without a DOM walk
+    clip_previews: Rc<RefCell<Vec<gtk::Video>>>,
+    /// Photo-strip thumbnails in the grid, so `update_thumbnail_visibility`
+    /// can start/cancel their image fetch without a DOM walk
+    thumbnail_slots: Rc<RefCell<Vec<Rc<ThumbnailSlot>>>>,
+    /// Ids of photos pinned to the front of the strip. Client-side only -
+    /// `connect_photo_action`'s callback is responsible for persisting the
+    /// pin server-side; this just drives the immediate badge and sort order.
+    pinned: Rc<RefCell<HashSet<String>>>,
+    /// Callback for the strip thumbnails' long-press/secondary-click context
+    /// menu actions (pin, edit, delete, mark for print)
+    photo_action_callback: Rc<RefCell<Option<Box<dyn Fn(&str, PhotoAction)>>>>,
+    /// Whether clip previews are allowed to play at all (the grid is
+    /// visible and the kiosk isn't mid-countdown/capture/lightbox); shared
+    /// so the photo strip's scroll and the window's focus handlers can
+    /// react to it too
+    previews_active: Rc<Cell<bool>>,
     /// Capture button
     pub capture_button: gtk::Button,
+    /// Expanding-ring indicator overlaid on the capture button while a
+    /// capture is in flight
+    capture_ring: CaptureRingIndicator,
+    /// Status label below the capture button, toggled by `set_capturing`
+    capture_status: gtk::Label,
     /// Start session button (welcome mode)
     pub start_button: gtk::Button,
     /// Phone count label
@@ -45,6 +131,9 @@ pub struct SessionWidgets {
     pub end_button: gtk::Button,
     /// QR panel (expandable)
     pub qr_panel: Rc<ExpandableQrPanel>,
+    /// Toggleable corner overlay reporting live pipeline diagnostics, shown
+    /// via a long-press on the phone count
+    diagnostics_overlay: DiagnosticsOverlay,
     /// Welcome content box
     welcome_box: gtk::Box,
     /// Countdown overlay
@@ -53,6 +142,16 @@ pub struct SessionWidgets {
     pub countdown_label: gtk::Label,
     /// Active countdown animation
     countdown_animation: Rc<RefCell<Option<adw::TimedAnimation>>>,
+    /// Clip view (for viewing a selected boomerang clip)
+    pub clip_video: gtk::Video,
+    /// Play/pause toggle for the clip view
+    clip_play_button: gtk::Button,
+    /// Seek slider for the clip view
+    clip_seek: gtk::Scale,
+    /// The `GtkMediaFile` currently loaded in the clip view, if any - shared
+    /// with the play/pause and seek controls so they always act on whatever
+    /// clip is currently showing
+    clip_media_file: Rc<RefCell<Option<gtk::MediaFile>>>,
 }
 
 /// Create the unified session screen
@@ -89,6 +188,66 @@ pub fn create_session_screen(
     photo_picture.add_css_class("main-photo");
     main_stack.add_named(&photo_picture, Some("photo"));
 
+    // Clip view (for viewing a selected boomerang clip) - unmuted, with a
+    // play/pause toggle and a seek slider underneath
+    let clip_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    clip_box.set_hexpand(true);
+    clip_box.set_vexpand(true);
+
+    let clip_video = gtk::Video::new();
+    clip_video.set_content_fit(gtk::ContentFit::Contain);
+    clip_video.set_hexpand(true);
+    clip_video.set_vexpand(true);
+    clip_video.set_autoplay(false);
+    clip_video.set_loop(true);
+    clip_video.add_css_class("main-clip");
+    clip_box.append(&clip_video);
+
+    let clip_controls = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+    clip_controls.add_css_class("clip-controls");
+    clip_controls.set_margin_start(24);
+    clip_controls.set_margin_end(24);
+    clip_controls.set_margin_bottom(16);
+
+    let clip_play_button = gtk::Button::from_icon_name("media-playback-pause-symbolic");
+    clip_play_button.add_css_class("clip-play-button");
+
+    let clip_seek = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 1.0, 0.01);
+    clip_seek.set_hexpand(true);
+    clip_seek.set_draw_value(false);
+
+    clip_controls.append(&clip_play_button);
+    clip_controls.append(&clip_seek);
+    clip_box.append(&clip_controls);
+
+    main_stack.add_named(&clip_box, Some("clip"));
+
+    let clip_media_file: Rc<RefCell<Option<gtk::MediaFile>>> = Rc::new(RefCell::new(None));
+
+    let media_file_for_toggle = clip_media_file.clone();
+    clip_play_button.connect_clicked(move |button| {
+        if let Some(media_file) = media_file_for_toggle.borrow().as_ref() {
+            if media_file.is_playing() {
+                media_file.pause();
+                button.set_icon_name("media-playback-start-symbolic");
+            } else {
+                media_file.play();
+                button.set_icon_name("media-playback-pause-symbolic");
+            }
+        }
+    });
+
+    let media_file_for_seek = clip_media_file.clone();
+    clip_seek.connect_change_value(move |_, _, value| {
+        if let Some(media_file) = media_file_for_seek.borrow().as_ref() {
+            let duration = media_file.duration();
+            if duration > 0 {
+                media_file.seek((value.clamp(0.0, 1.0) * duration as f64) as i64);
+            }
+        }
+        glib::Propagation::Proceed
+    });
+
     // Start with live view
     main_stack.set_visible_child_name("live");
     overlay.set_child(Some(&main_stack));
@@ -116,6 +275,18 @@ pub fn create_session_screen(
     phone_box.append(&phone_count_label);
     overlay.add_overlay(&phone_box);
 
+    // Long-pressing the phone count toggles the diagnostics overlay - an
+    // operator affordance, not meant for guests to stumble onto
+    let diagnostics_overlay = DiagnosticsOverlay::new();
+    overlay.add_overlay(diagnostics_overlay.widget());
+
+    let diagnostics_for_toggle = diagnostics_overlay.clone();
+    let diagnostics_long_press = gtk::GestureLongPress::new();
+    diagnostics_long_press.connect_pressed(move |_, _, _| {
+        diagnostics_for_toggle.toggle();
+    });
+    phone_box.add_controller(diagnostics_long_press);
+
     // === Floating end session button (top-left, below status) - session only ===
     let end_button = gtk::Button::with_label("End Session");
     end_button.add_css_class("end-button");
@@ -163,7 +334,30 @@ pub fn create_session_screen(
     capture_button.set_valign(gtk::Align::End);
     capture_button.set_margin_bottom(160);
     capture_button.set_visible(false);
-    overlay.add_overlay(&capture_button);
+
+    // Ring indicator overlaid on the capture button, started/stopped
+    // alongside its disabled state while a capture is in flight. Wrapped
+    // around the button rather than placed directly on `overlay` so it
+    // inherits the button's own visibility (shrinking to nothing whenever
+    // the button itself is hidden) without needing to track it separately.
+    let capture_ring = CaptureRingIndicator::new();
+    let capture_ring_overlay = gtk::Overlay::new();
+    capture_ring_overlay.set_halign(gtk::Align::Center);
+    capture_ring_overlay.set_valign(gtk::Align::End);
+    capture_ring_overlay.set_margin_bottom(160);
+    capture_ring_overlay.set_child(Some(&capture_button));
+    capture_ring_overlay.add_overlay(capture_ring.widget());
+
+    overlay.add_overlay(&capture_ring_overlay);
+
+    // Status label shown under the capture button while a capture is in
+    // flight (button tapped, waiting on the server-driven countdown/upload)
+    let capture_status = widgets::create_capture_status(false);
+    capture_status.set_halign(gtk::Align::Center);
+    capture_status.set_valign(gtk::Align::End);
+    capture_status.set_margin_bottom(120);
+    capture_status.set_visible(false);
+    overlay.add_overlay(&capture_status);
 
     // === QR panel (top-right, small and expandable) ===
     let qr_panel = ExpandableQrPanel::new(ctx);
@@ -199,6 +393,51 @@ pub fn create_session_screen(
 
     overlay.add_overlay(&photo_strip);
 
+    let clip_previews: Rc<RefCell<Vec<gtk::Video>>> = Rc::new(RefCell::new(Vec::new()));
+    let thumbnail_slots: Rc<RefCell<Vec<Rc<ThumbnailSlot>>>> = Rc::new(RefCell::new(Vec::new()));
+    let previews_active: Rc<Cell<bool>> = Rc::new(Cell::new(true));
+    let pinned: Rc<RefCell<HashSet<String>>> = Rc::new(RefCell::new(HashSet::new()));
+    let photo_action_callback: Rc<RefCell<Option<Box<dyn Fn(&str, PhotoAction)>>>> =
+        Rc::new(RefCell::new(None));
+
+    // Only the clip tiles and thumbnails actually scrolled into the photo
+    // strip's viewport should be decoding/fetching, so re-check on every scroll
+    let clip_previews_for_scroll = clip_previews.clone();
+    let thumbnail_slots_for_scroll = thumbnail_slots.clone();
+    let previews_active_for_scroll = previews_active.clone();
+    let photo_strip_for_scroll = photo_strip.clone();
+    photo_strip.hadjustment().connect_value_changed(move |_| {
+        update_clip_visibility(
+            &photo_strip_for_scroll,
+            &clip_previews_for_scroll,
+            &previews_active_for_scroll,
+        );
+        update_thumbnail_visibility(&photo_strip_for_scroll, &thumbnail_slots_for_scroll);
+    });
+
+    // Pause every preview while the kiosk window isn't focused, resuming
+    // whichever tiles are in view once it regains focus
+    let clip_previews_for_focus = clip_previews.clone();
+    let previews_active_for_focus = previews_active.clone();
+    let photo_strip_for_focus = photo_strip.clone();
+    overlay.connect_map(move |widget| {
+        let Some(window) = widget.root().and_then(|r| r.downcast::<gtk::Window>().ok()) else {
+            return;
+        };
+        let clip_previews = clip_previews_for_focus.clone();
+        let previews_active = previews_active_for_focus.clone();
+        let photo_strip = photo_strip_for_focus.clone();
+        window.connect_notify_local(Some("is-active"), move |window, _| {
+            if window.is_active() {
+                update_clip_visibility(&photo_strip, &clip_previews, &previews_active);
+            } else {
+                for video in clip_previews.borrow().iter() {
+                    video.pause();
+                }
+            }
+        });
+    });
+
     // === Countdown overlay (center, over everything) ===
     let countdown_overlay = gtk::Box::new(gtk::Orientation::Vertical, 0);
     countdown_overlay.add_css_class("countdown-overlay");
@@ -216,6 +455,187 @@ pub fn create_session_screen(
 
     overlay.add_overlay(&countdown_overlay);
 
+    // === Fullscreen media viewer (above all floating controls) ===
+    let current_selection: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
+    let current_photos: Rc<RefCell<Vec<PhotoInfo>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let viewer_picture = gtk::Picture::new();
+    viewer_picture.set_content_fit(gtk::ContentFit::Contain);
+    viewer_picture.set_hexpand(true);
+    viewer_picture.set_vexpand(true);
+    viewer_picture.add_css_class("viewer-image");
+
+    let viewer_video = gtk::Video::new();
+    viewer_video.set_hexpand(true);
+    viewer_video.set_vexpand(true);
+    viewer_video.set_autoplay(true);
+    viewer_video.set_loop(true);
+    viewer_video.add_css_class("viewer-video");
+
+    let viewer_media_stack = gtk::Stack::new();
+    viewer_media_stack.set_hexpand(true);
+    viewer_media_stack.set_vexpand(true);
+    viewer_media_stack.add_named(&viewer_picture, Some("image"));
+    viewer_media_stack.add_named(&viewer_video, Some("video"));
+
+    let viewer_bin = ScaleBin::new(&viewer_media_stack);
+    viewer_bin.set_hexpand(true);
+    viewer_bin.set_vexpand(true);
+
+    // Edit button, floated over the viewed image so it doesn't compete with
+    // the swipe-down-to-close and pinch-to-zoom gestures on the image itself
+    let edit_button = gtk::Button::from_icon_name("document-edit-symbolic");
+    edit_button.add_css_class("viewer-edit-button");
+    edit_button.add_css_class("floating-button");
+    edit_button.set_halign(gtk::Align::End);
+    edit_button.set_valign(gtk::Align::Start);
+    edit_button.set_margin_end(24);
+    edit_button.set_margin_top(24);
+
+    let viewer_bin_overlay = gtk::Overlay::new();
+    viewer_bin_overlay.set_child(Some(&viewer_bin));
+    viewer_bin_overlay.add_overlay(&edit_button);
+
+    let viewer_overlay = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    viewer_overlay.add_css_class("media-viewer");
+    viewer_overlay.set_hexpand(true);
+    viewer_overlay.set_vexpand(true);
+    viewer_overlay.set_visible(false);
+    viewer_overlay.append(&viewer_bin_overlay);
+
+    let media_viewer = MediaViewerWidgets {
+        overlay: viewer_overlay,
+        bin: viewer_bin,
+        media_stack: viewer_media_stack,
+        picture: viewer_picture,
+        video: viewer_video,
+    };
+
+    // Pinch-to-zoom and pan, mirroring the lightbox's `ScaleBin` handling
+    let zoom_gesture = gtk::GestureZoom::new();
+    let zoom_base_scale = Rc::new(Cell::new(VIEWER_MIN_ZOOM_SCALE));
+    let bin_zoom_begin = media_viewer.bin.clone();
+    let zoom_base_scale_begin = zoom_base_scale.clone();
+    zoom_gesture.connect_begin(move |_, _| {
+        zoom_base_scale_begin.set(bin_zoom_begin.scale());
+    });
+    let bin_zoom = media_viewer.bin.clone();
+    zoom_gesture.connect_scale_changed(move |_, scale| {
+        let new_scale =
+            (zoom_base_scale.get() * scale).clamp(VIEWER_MIN_ZOOM_SCALE, VIEWER_MAX_ZOOM_SCALE);
+        bin_zoom.set_scale(new_scale);
+    });
+    media_viewer.bin.add_controller(zoom_gesture);
+
+    let drag_gesture = gtk::GestureDrag::new();
+    let drag_base_offset = Rc::new(Cell::new((0.0_f64, 0.0_f64)));
+    let bin_drag_begin = media_viewer.bin.clone();
+    let drag_base_offset_begin = drag_base_offset.clone();
+    drag_gesture.connect_drag_begin(move |_, _, _| {
+        drag_base_offset_begin.set(bin_drag_begin.translate());
+    });
+    let bin_drag = media_viewer.bin.clone();
+    drag_gesture.connect_drag_update(move |_, dx, dy| {
+        if bin_drag.scale() > VIEWER_MIN_ZOOM_SCALE + VIEWER_ZOOM_EPSILON {
+            let (base_x, base_y) = drag_base_offset.get();
+            bin_drag.set_translate(base_x + dx, base_y + dy);
+        }
+    });
+    media_viewer.bin.add_controller(drag_gesture);
+
+    // Tap the background to close, unless the tap is actually the end of a
+    // zoomed-in pan
+    let media_viewer_for_tap = media_viewer.clone();
+    let tap_gesture = gtk::GestureClick::new();
+    tap_gesture.connect_released(move |_, _, _, _| {
+        if media_viewer_for_tap.bin.scale() <= VIEWER_MIN_ZOOM_SCALE + VIEWER_ZOOM_EPSILON {
+            media_viewer_hide(&media_viewer_for_tap);
+        }
+    });
+    media_viewer.overlay.add_controller(tap_gesture);
+
+    // Left/Right arrow keys and Escape navigate/close
+    let media_viewer_for_key = media_viewer.clone();
+    let current_photos_for_key = current_photos.clone();
+    let current_selection_for_key = current_selection.clone();
+    let ctx_for_key = ctx.clone();
+    let key_controller = gtk::EventControllerKey::new();
+    key_controller.connect_key_pressed(move |_, key, _, _| match key {
+        gtk::gdk::Key::Escape => {
+            media_viewer_hide(&media_viewer_for_key);
+            glib::Propagation::Stop
+        }
+        gtk::gdk::Key::Left => {
+            media_viewer_navigate(
+                &ctx_for_key,
+                &media_viewer_for_key,
+                &current_photos_for_key,
+                &current_selection_for_key,
+                -1,
+            );
+            glib::Propagation::Stop
+        }
+        gtk::gdk::Key::Right => {
+            media_viewer_navigate(
+                &ctx_for_key,
+                &media_viewer_for_key,
+                &current_photos_for_key,
+                &current_selection_for_key,
+                1,
+            );
+            glib::Propagation::Stop
+        }
+        _ => glib::Propagation::Proceed,
+    });
+    media_viewer.overlay.add_controller(key_controller);
+
+    // Left/right swipe navigates, a downward swipe closes
+    let media_viewer_for_swipe = media_viewer.clone();
+    let current_photos_for_swipe = current_photos.clone();
+    let current_selection_for_swipe = current_selection.clone();
+    let ctx_for_swipe = ctx.clone();
+    let swipe_gesture = gtk::GestureSwipe::new();
+    swipe_gesture.connect_swipe(move |_, vx, vy| {
+        if media_viewer_for_swipe.bin.scale() > VIEWER_MIN_ZOOM_SCALE + VIEWER_ZOOM_EPSILON {
+            return;
+        }
+        if vy.abs() > vx.abs() && vy > VIEWER_SWIPE_VELOCITY_THRESHOLD {
+            media_viewer_hide(&media_viewer_for_swipe);
+        } else if vx.abs() > VIEWER_SWIPE_VELOCITY_THRESHOLD {
+            let delta = if vx < 0.0 { 1 } else { -1 };
+            media_viewer_navigate(
+                &ctx_for_swipe,
+                &media_viewer_for_swipe,
+                &current_photos_for_swipe,
+                &current_selection_for_swipe,
+                delta,
+            );
+        }
+    });
+    media_viewer.overlay.add_controller(swipe_gesture);
+
+    overlay.add_overlay(&media_viewer.overlay);
+
+    // === Full-screen photo editor (above the media viewer) ===
+    let photo_editor = PhotoEditor::new();
+    overlay.add_overlay(&photo_editor.overlay);
+
+    let ctx_for_edit = ctx.clone();
+    let current_photos_for_edit = current_photos.clone();
+    let current_selection_for_edit = current_selection.clone();
+    let photo_editor_for_edit = photo_editor.clone();
+    edit_button.connect_clicked(move |_| {
+        let Some(index) = *current_selection_for_edit.borrow() else {
+            return;
+        };
+        let Some(photo) = current_photos_for_edit.borrow().get(index).cloned() else {
+            return;
+        };
+        if photo.kind == MediaKind::Image {
+            photo_editor_for_edit.open(&ctx_for_edit, &photo.id, &photo.web_url);
+        }
+    });
+
     SessionWidgets {
         overlay,
         main_stack,
@@ -224,17 +644,32 @@ pub fn create_session_screen(
         photo_strip,
         photo_strip_inner: Rc::new(RefCell::new(photo_strip_inner)),
         loaded_photos: Rc::new(RefCell::new(Vec::new())),
-        current_selection: Rc::new(RefCell::new(None)),
+        current_selection,
+        current_photos,
+        media_viewer,
+        photo_editor,
+        clip_previews,
+        thumbnail_slots,
+        previews_active,
+        pinned,
+        photo_action_callback,
         capture_button,
+        capture_ring,
+        capture_status,
         start_button,
         phone_count_label,
         phone_box,
         end_button,
         qr_panel,
+        diagnostics_overlay,
         welcome_box,
         countdown_overlay,
         countdown_label,
         countdown_animation: Rc::new(RefCell::new(None)),
+        clip_video,
+        clip_play_button,
+        clip_seek,
+        clip_media_file,
     }
 }
 
@@ -278,9 +713,21 @@ impl SessionWidgets {
         self.main_stack.set_visible_child_name("live");
     }
 
-    /// Update phone count display
+    /// Update phone count display, shrinking the QR panel once a phone has
+    /// joined since it no longer needs to be shown prominently
     pub fn set_phone_count(&self, count: u32) {
         self.phone_count_label.set_text(&format!("{} connected", count));
+
+        if count > 0 {
+            self.qr_panel.collapse();
+        }
+    }
+
+    /// Refresh the diagnostics overlay's text from the latest reported
+    /// stream info. A no-op on visibility - the overlay stays hidden until
+    /// toggled regardless of how often this is called.
+    pub fn set_stream_info(&self, info: Option<&StreamInfo>) {
+        self.diagnostics_overlay.set_info(info);
     }
 
     /// Update the photo strip in-place (no flashing)
@@ -295,12 +742,26 @@ impl SessionWidgets {
         F1: Fn() + Clone + 'static,
         F2: Fn(usize) + Clone + 'static,
     {
+        // Pinned photos sort to the front (stable, so relative order among
+        // pinned and among unpinned photos is preserved); the id of the
+        // selected photo is resolved up front so the rest of this method can
+        // key off it instead of `selection`'s position in the caller's
+        // (unsorted) list, which pin/delete would otherwise invalidate.
+        let selected_id = selection.and_then(|i| photos.get(i)).map(|p| p.id.clone());
+        let mut ordered: Vec<PhotoInfo> = photos.to_vec();
+        {
+            let pinned = self.pinned.borrow();
+            ordered.sort_by_key(|p| !pinned.contains(&p.id));
+        }
+
+        *self.current_photos.borrow_mut() = ordered.clone();
+
         let mut loaded = self.loaded_photos.borrow_mut();
         let mut current_sel = self.current_selection.borrow_mut();
         let inner = self.photo_strip_inner.borrow();
 
         // Check if we need to add new photos
-        let photo_urls: Vec<String> = photos.iter().map(|p| p.thumbnail_url.clone()).collect();
+        let photo_urls: Vec<String> = ordered.iter().map(|p| p.thumbnail_url.clone()).collect();
         let needs_rebuild = loaded.is_empty()
             || photo_urls.len() < loaded.len()
             || (!photo_urls.is_empty() && !loaded.is_empty() && photo_urls[0] != loaded[0]);
@@ -312,46 +773,108 @@ impl SessionWidgets {
                 inner.remove(&child);
             }
             loaded.clear();
+            self.clip_previews.borrow_mut().clear();
+            for slot in self.thumbnail_slots.borrow().iter() {
+                stop_thumbnail_fetch(slot);
+            }
+            self.thumbnail_slots.borrow_mut().clear();
 
             // Add LIVE tile
             let live_tile = create_live_tile(selection.is_none(), on_live.clone());
             inner.append(&live_tile);
 
             // Add photo thumbnails
-            for (idx, photo) in photos.iter().enumerate() {
-                let is_selected = selection == Some(idx);
-                let thumb = create_photo_thumbnail(ctx, photo, is_selected, {
-                    let on_click = on_photo.clone();
-                    move || on_click(idx)
-                });
+            for (idx, photo) in ordered.iter().enumerate() {
+                let is_selected = Some(&photo.id) == selected_id.as_ref();
+                let thumb = create_photo_thumbnail(
+                    ctx,
+                    photo,
+                    is_selected,
+                    &self.photo_strip,
+                    &self.previews_active,
+                    &self.clip_previews,
+                    &self.thumbnail_slots,
+                    &self.pinned,
+                    &self.photo_action_callback,
+                    {
+                        let on_click = on_photo.clone();
+                        let ctx = ctx.clone();
+                        let media_viewer = self.media_viewer.clone();
+                        let current_photos = self.current_photos.clone();
+                        let current_selection = self.current_selection.clone();
+                        move || {
+                            media_viewer_show(
+                                &ctx,
+                                &media_viewer,
+                                &current_photos,
+                                &current_selection,
+                                idx,
+                            );
+                            on_click(idx)
+                        }
+                    },
+                );
                 inner.append(&thumb);
                 loaded.push(photo.thumbnail_url.clone());
             }
+            update_thumbnail_visibility(&self.photo_strip, &self.thumbnail_slots);
 
-            *current_sel = selection;
+            *current_sel = selected_id
+                .as_ref()
+                .and_then(|id| ordered.iter().position(|p| &p.id == id));
         } else {
             // Incremental update - just add new photos and update selection
             let existing_count = loaded.len();
 
             // Add any new photos with slide-in animation
-            for (idx, photo) in photos.iter().enumerate().skip(existing_count) {
-                let is_selected = selection == Some(idx);
-                let thumb = create_photo_thumbnail(ctx, photo, is_selected, {
-                    let on_click = on_photo.clone();
-                    move || on_click(idx)
-                });
+            for (idx, photo) in ordered.iter().enumerate().skip(existing_count) {
+                let is_selected = Some(&photo.id) == selected_id.as_ref();
+                let thumb = create_photo_thumbnail(
+                    ctx,
+                    photo,
+                    is_selected,
+                    &self.photo_strip,
+                    &self.previews_active,
+                    &self.clip_previews,
+                    &self.thumbnail_slots,
+                    &self.pinned,
+                    &self.photo_action_callback,
+                    {
+                        let on_click = on_photo.clone();
+                        let ctx = ctx.clone();
+                        let media_viewer = self.media_viewer.clone();
+                        let current_photos = self.current_photos.clone();
+                        let current_selection = self.current_selection.clone();
+                        move || {
+                            media_viewer_show(
+                                &ctx,
+                                &media_viewer,
+                                &current_photos,
+                                &current_selection,
+                                idx,
+                            );
+                            on_click(idx)
+                        }
+                    },
+                );
                 inner.append(&thumb);
                 loaded.push(photo.thumbnail_url.clone());
 
                 // Animate the new thumbnail sliding in
                 animations::slide_in_from_right(&thumb, animations::duration::NORMAL);
             }
-
-            // Update selection if changed
-            if *current_sel != selection {
+            update_thumbnail_visibility(&self.photo_strip, &self.thumbnail_slots);
+
+            // Update selection if changed, keyed off the photo's stable id
+            // (via its `widget_name`, set at creation time) rather than its
+            // position - pin/delete reorder the strip without rebuilding it
+            let new_sel = selected_id
+                .as_ref()
+                .and_then(|id| ordered.iter().position(|p| &p.id == id));
+            if *current_sel != new_sel {
                 // Update LIVE tile selection
                 if let Some(live_tile) = inner.first_child() {
-                    if selection.is_none() {
+                    if selected_id.is_none() {
                         live_tile.add_css_class("selected");
                     } else {
                         live_tile.remove_css_class("selected");
@@ -364,8 +887,10 @@ impl SessionWidgets {
                 while let Some(widget) = child {
                     if idx > 0 {
                         // Skip LIVE tile (idx 0)
-                        let photo_idx = idx - 1;
-                        if selection == Some(photo_idx) {
+                        let is_selected = selected_id
+                            .as_deref()
+                            .is_some_and(|id| widget.widget_name() == id);
+                        if is_selected {
                             widget.add_css_class("selected");
                         } else {
                             widget.remove_css_class("selected");
@@ -375,11 +900,24 @@ impl SessionWidgets {
                     idx += 1;
                 }
 
-                *current_sel = selection;
+                *current_sel = new_sel;
             }
         }
     }
 
+    /// Allow or forbid clip previews from playing at all - used when the
+    /// grid itself isn't on screen (countdown, capture, or the lightbox) or
+    /// the session has ended. Tiles scrolled out of the photo strip stay
+    /// paused even when `active` is true; see `update_clip_visibility`.
+    pub fn set_previews_active(&self, active: bool) {
+        self.previews_active.set(active);
+        update_clip_visibility(
+            &self.photo_strip,
+            &self.clip_previews,
+            &self.previews_active,
+        );
+    }
+
     /// Show live video view
     pub fn show_live_view(&self) {
         // Only switch if not already on live
@@ -401,11 +939,21 @@ impl SessionWidgets {
         }
         self.capture_button.set_visible(false);
 
-        // Load the photo
+        // Load the photo, serving straight from the cache (no fade - it's
+        // already decoded) on a hit
         let url = config::photo_url(&photo.web_url);
+
+        if let Some(texture) = ctx.session_texture_cache.get(&url) {
+            self.photo_picture.set_paintable(Some(&texture));
+            self.photo_picture.set_opacity(1.0);
+            return;
+        }
+
         let picture = self.photo_picture.clone();
         let api = ctx.api.clone();
         let runtime = ctx.runtime.clone();
+        let ctx = ctx.clone();
+        let cache_url = url.clone();
 
         glib::spawn_future_local(async move {
             let result = runtime.spawn(async move { api.fetch_image(&url).await }).await;
@@ -419,6 +967,7 @@ impl SessionWidgets {
                         None::<&gtk::gio::Cancellable>,
                     ) {
                         let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
+                        ctx.session_texture_cache.insert(cache_url, texture.clone());
                         picture.set_paintable(Some(&texture));
                         // Fade in the new photo
                         animations::fade_in(&picture, animations::duration::FAST);
@@ -430,6 +979,79 @@ impl SessionWidgets {
         });
     }
 
+    /// Show a boomerang clip in the main area with unmuted, looping
+    /// playback, a play/pause toggle and a seek slider - the clip
+    /// counterpart to `show_photo`.
+    pub fn show_clip(&self, ctx: &Rc<AppContext>, photo: &PhotoInfo) {
+        if self.main_stack.visible_child_name().as_deref() != Some("clip") {
+            self.main_stack.set_visible_child_name("clip");
+        }
+        self.capture_button.set_visible(false);
+
+        let full_url = config::photo_url(&photo.web_url);
+        let ext = Path::new(&photo.web_url)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("mp4")
+            .to_string();
+        let temp_path = std::env::temp_dir().join(format!("picpop-main-clip-{}.{}", photo.id, ext));
+        let video = self.clip_video.clone();
+        let play_button = self.clip_play_button.clone();
+        let seek = self.clip_seek.clone();
+        let media_file_slot = self.clip_media_file.clone();
+        let api = ctx.api.clone();
+        let runtime = ctx.runtime.clone();
+
+        glib::spawn_future_local(async move {
+            let result = runtime.spawn(async move { api.fetch_image(&full_url).await }).await;
+
+            match result {
+                Ok(Ok(bytes)) => {
+                    if let Err(e) = std::fs::write(&temp_path, &bytes) {
+                        log::error!("Failed to write clip to temp file: {}", e);
+                        return;
+                    }
+                    let media_file = gtk::MediaFile::for_filename(&temp_path);
+                    media_file.set_loop(true);
+                    media_file.set_muted(false);
+
+                    let seek_for_sync = seek.clone();
+                    media_file.connect_timestamp_notify(move |mf| {
+                        let duration = mf.duration();
+                        if duration > 0 {
+                            seek_for_sync.set_value(mf.timestamp() as f64 / duration as f64);
+                        }
+                    });
+
+                    video.set_media_stream(Some(&media_file));
+                    media_file.play();
+                    play_button.set_icon_name("media-playback-pause-symbolic");
+                    *media_file_slot.borrow_mut() = Some(media_file);
+                }
+                Ok(Err(e)) => log::error!("Failed to load clip: {}", e),
+                Err(e) => log::error!("Task join error: {}", e),
+            }
+        });
+    }
+
+    /// Open the fullscreen media viewer on `current_photos[index]` (the
+    /// photo list from the most recent `update_photos` call, in its
+    /// pinned-first display order).
+    pub fn show_media_viewer(&self, ctx: &Rc<AppContext>, index: usize) {
+        media_viewer_show(
+            ctx,
+            &self.media_viewer,
+            &self.current_photos,
+            &self.current_selection,
+            index,
+        );
+    }
+
+    /// Close the fullscreen media viewer.
+    pub fn hide_media_viewer(&self) {
+        media_viewer_hide(&self.media_viewer);
+    }
+
     /// Show countdown overlay with animated number
     pub fn show_countdown(&self, value: u32) {
         // Cancel any existing countdown animation
@@ -445,6 +1067,7 @@ impl SessionWidgets {
         }
 
         self.capture_button.set_sensitive(false);
+        self.capture_ring.start();
 
         // Animate the countdown number (scale down + fade in)
         self.countdown_label.set_text(&value.to_string());
@@ -486,6 +1109,19 @@ impl SessionWidgets {
             animations::fade_out(&self.countdown_overlay, animations::duration::FAST);
         }
         self.capture_button.set_sensitive(true);
+        self.capture_ring.stop();
+    }
+
+    /// Toggle the "Taking photos..." status shown under the capture button
+    /// for the brief window between tapping capture and the first
+    /// server-driven countdown tick
+    pub fn set_capturing(&self, capturing: bool) {
+        self.capture_status.set_visible(capturing);
+        self.capture_status.set_text(if capturing {
+            "Taking photos..."
+        } else {
+            "Tap to capture!"
+        });
     }
 
     /// Show processing state
@@ -540,6 +1176,31 @@ impl SessionWidgets {
         gesture.connect_released(move |_, _, _, _| callback());
         self.photo_picture.add_controller(gesture);
     }
+
+    /// Connect the strip thumbnails' long-press/secondary-click context menu
+    /// actions (pin, edit, delete, mark for print). `callback` receives the photo's
+    /// stable id and the action chosen.
+    pub fn connect_photo_action<F>(&self, callback: F)
+    where
+        F: Fn(&str, PhotoAction) + 'static,
+    {
+        *self.photo_action_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Open the full-screen photo editor on `photo`.
+    pub fn open_photo_editor(&self, ctx: &Rc<AppContext>, photo: &PhotoInfo) {
+        self.photo_editor.open(ctx, &photo.id, &photo.web_url);
+    }
+
+    /// Connect the callback invoked with `(photo_id, edited_jpeg_bytes)` when
+    /// the user confirms an edit in the photo editor, so the app can upload
+    /// the edited version and refresh the cached texture/thumbnail in place.
+    pub fn connect_photo_edited<F>(&self, callback: F)
+    where
+        F: Fn(&str, Vec<u8>) + 'static,
+    {
+        self.photo_editor.connect_confirm(callback);
+    }
 }
 
 /// Create the LIVE tile button
@@ -576,39 +1237,111 @@ where
     button
 }
 
-/// Create a single photo thumbnail
+/// A photo-strip thumbnail whose image fetch is deferred until it scrolls
+/// into the strip's viewport (plus `STRIP_PREFETCH_MARGIN_PX`), and
+/// cancelled if it scrolls back out before finishing - mirrors
+/// `clip_previews`'s play/pause-by-visibility handling, but gating a
+/// network fetch instead of video playback.
+struct ThumbnailSlot {
+    picture: gtk::Picture,
+    url: String,
+    ctx: Rc<AppContext>,
+    loaded: Cell<bool>,
+    abort_handle: RefCell<Option<AbortHandle>>,
+}
+
+/// Create a single photo or clip thumbnail
 fn create_photo_thumbnail<F>(
     ctx: &Rc<AppContext>,
     photo: &PhotoInfo,
     is_selected: bool,
+    photo_strip: &gtk::ScrolledWindow,
+    previews_active: &Rc<Cell<bool>>,
+    clip_previews: &Rc<RefCell<Vec<gtk::Video>>>,
+    thumbnail_slots: &Rc<RefCell<Vec<Rc<ThumbnailSlot>>>>,
+    pinned: &Rc<RefCell<HashSet<String>>>,
+    photo_action_callback: &Rc<RefCell<Option<Box<dyn Fn(&str, PhotoAction)>>>>,
     on_click: F,
 ) -> gtk::Button
 where
     F: Fn() + 'static,
 {
+    if photo.kind == MediaKind::Clip {
+        return create_clip_thumbnail(
+            ctx,
+            photo,
+            is_selected,
+            photo_strip,
+            previews_active,
+            clip_previews,
+            pinned,
+            photo_action_callback,
+            on_click,
+        );
+    }
+
     let button = gtk::Button::new();
     button.add_css_class("photo-thumbnail");
+    button.set_widget_name(&photo.id);
     if is_selected {
         button.add_css_class("selected");
     }
     button.set_size_request(100, 100);
+    build_photo_context_menu(&button, photo.id.clone(), pinned, photo_action_callback);
 
     let picture = gtk::Picture::new();
     picture.set_size_request(100, 100);
     picture.set_content_fit(gtk::ContentFit::Cover);
     picture.set_opacity(0.0); // Start invisible for fade-in
 
-    // Load thumbnail using spawn_future_local to stay on main thread
+    // Serve straight from the cache (no fade - it's already decoded) on a
+    // hit; otherwise register a slot and defer the fetch until
+    // `update_thumbnail_visibility` sees this tile scroll into view.
     let url = config::photo_url(&photo.thumbnail_url);
-    let picture_clone = picture.clone();
-    let api = ctx.api.clone();
-    let runtime = ctx.runtime.clone();
 
+    if let Some(texture) = ctx.session_texture_cache.get(&url) {
+        picture.set_paintable(Some(&texture));
+        picture.set_opacity(1.0);
+        button.set_child(Some(&picture));
+        button.connect_clicked(move |_| on_click());
+        return button;
+    }
+
+    thumbnail_slots.borrow_mut().push(Rc::new(ThumbnailSlot {
+        picture: picture.clone(),
+        url,
+        ctx: ctx.clone(),
+        loaded: Cell::new(false),
+        abort_handle: RefCell::new(None),
+    }));
+
+    button.set_child(Some(&picture));
+    button.connect_clicked(move |_| on_click());
+
+    button
+}
+
+/// Start fetching `slot`'s image if it isn't already loaded or in flight.
+fn start_thumbnail_fetch(slot: &Rc<ThumbnailSlot>) {
+    if slot.loaded.get() || slot.abort_handle.borrow().is_some() {
+        return;
+    }
+
+    let api = slot.ctx.api.clone();
+    let runtime = slot.ctx.runtime.clone();
+    let url = slot.url.clone();
+
+    let (fetch, abort_handle) =
+        abortable(async move { runtime.spawn(async move { api.fetch_image(&url).await }).await });
+    slot.abort_handle.replace(Some(abort_handle));
+
+    let slot = slot.clone();
     glib::spawn_future_local(async move {
-        let result = runtime.spawn(async move { api.fetch_image(&url).await }).await;
+        let result = fetch.await;
+        slot.abort_handle.replace(None);
 
         match result {
-            Ok(Ok(bytes)) => {
+            Ok(Ok(Ok(bytes))) => {
                 let gbytes = glib::Bytes::from(&bytes);
                 let stream = gtk::gio::MemoryInputStream::from_bytes(&gbytes);
                 if let Ok(pixbuf) = gtk::gdk_pixbuf::Pixbuf::from_stream(
@@ -616,22 +1349,427 @@ where
                     None::<&gtk::gio::Cancellable>,
                 ) {
                     let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
-                    picture_clone.set_paintable(Some(&texture));
+                    slot.ctx
+                        .session_texture_cache
+                        .insert(slot.url.clone(), texture.clone());
+                    slot.picture.set_paintable(Some(&texture));
+                    slot.loaded.set(true);
                     // Fade in the thumbnail once loaded
-                    animations::fade_in(&picture_clone, animations::duration::FAST);
+                    animations::fade_in(&slot.picture, animations::duration::FAST);
                 }
             }
-            Ok(Err(e)) => {
+            Ok(Ok(Err(e))) => {
                 log::error!("Failed to load thumbnail: {}", e);
             }
+            Ok(Err(e)) => {
+                log::error!("Task join error: {}", e);
+            }
+            Err(futures_util::future::Aborted) => {
+                // Scrolled out of view before the fetch finished - it will
+                // restart if scrolled back into view.
+            }
+        }
+    });
+}
+
+/// Cancel `slot`'s in-flight fetch, if any, so a tile scrolled far
+/// off-screen doesn't keep downloading.
+fn stop_thumbnail_fetch(slot: &Rc<ThumbnailSlot>) {
+    if let Some(handle) = slot.abort_handle.borrow_mut().take() {
+        handle.abort();
+    }
+}
+
+/// Start or cancel each thumbnail's image fetch depending on whether it's
+/// scrolled into the photo strip's viewport (plus `STRIP_PREFETCH_MARGIN_PX`)
+/// - called whenever the strip scrolls and once right after new thumbnails
+/// are added to it.
+fn update_thumbnail_visibility(
+    photo_strip: &gtk::ScrolledWindow,
+    thumbnail_slots: &Rc<RefCell<Vec<Rc<ThumbnailSlot>>>>,
+) {
+    let viewport_width = photo_strip.width() as f32;
+
+    for slot in thumbnail_slots.borrow().iter() {
+        let visible = slot
+            .picture
+            .compute_bounds(photo_strip)
+            .map(|bounds| {
+                bounds.x() + bounds.width() > -STRIP_PREFETCH_MARGIN_PX
+                    && bounds.x() < viewport_width + STRIP_PREFETCH_MARGIN_PX
+            })
+            .unwrap_or(false);
+
+        if visible {
+            start_thumbnail_fetch(slot);
+        } else {
+            stop_thumbnail_fetch(slot);
+        }
+    }
+}
+
+/// Create a clip thumbnail showing a muted, looping inline preview instead
+/// of a static image - mirrors the lightbox's own clip playback, but always
+/// muted and only playing once the tile is both allowed to preview
+/// (`previews_active`) and actually scrolled into the photo strip's
+/// viewport, so off-screen tiles don't keep decoding.
+fn create_clip_thumbnail<F>(
+    ctx: &Rc<AppContext>,
+    photo: &PhotoInfo,
+    is_selected: bool,
+    photo_strip: &gtk::ScrolledWindow,
+    previews_active: &Rc<Cell<bool>>,
+    clip_previews: &Rc<RefCell<Vec<gtk::Video>>>,
+    pinned: &Rc<RefCell<HashSet<String>>>,
+    photo_action_callback: &Rc<RefCell<Option<Box<dyn Fn(&str, PhotoAction)>>>>,
+    on_click: F,
+) -> gtk::Button
+where
+    F: Fn() + 'static,
+{
+    let button = gtk::Button::new();
+    button.add_css_class("photo-thumbnail");
+    button.set_widget_name(&photo.id);
+    if is_selected {
+        button.add_css_class("selected");
+    }
+    button.set_size_request(100, 100);
+    build_photo_context_menu(&button, photo.id.clone(), pinned, photo_action_callback);
+
+    let video = gtk::Video::new();
+    video.set_size_request(100, 100);
+    video.set_content_fit(gtk::ContentFit::Cover);
+    video.set_autoplay(false);
+    video.set_loop(true);
+    video.set_opacity(0.0); // Start invisible for fade-in
+
+    clip_previews.borrow_mut().push(video.clone());
+
+    let full_url = config::photo_url(&photo.web_url);
+    let ext = Path::new(&photo.web_url)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_string();
+    let temp_path = std::env::temp_dir().join(format!("picpop-grid-clip-{}.{}", photo.id, ext));
+    let video_clone = video.clone();
+    let photo_strip_clone = photo_strip.clone();
+    let previews_active_clone = previews_active.clone();
+    let clip_previews_clone = clip_previews.clone();
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime.spawn(async move { api.fetch_image(&full_url).await }).await;
+
+        match result {
+            Ok(Ok(bytes)) => {
+                if let Err(e) = std::fs::write(&temp_path, &bytes) {
+                    log::error!("Failed to write clip preview to temp file: {}", e);
+                    return;
+                }
+                let media_file = gtk::MediaFile::for_filename(&temp_path);
+                media_file.set_loop(true);
+                media_file.set_muted(true);
+                video_clone.set_media_stream(Some(&media_file));
+                animations::fade_in(&video_clone, animations::duration::FAST);
+                update_clip_visibility(
+                    &photo_strip_clone,
+                    &clip_previews_clone,
+                    &previews_active_clone,
+                );
+            }
+            Ok(Err(e)) => {
+                log::error!("Failed to load clip preview: {}", e);
+            }
             Err(e) => {
                 log::error!("Task join error: {}", e);
             }
         }
     });
 
-    button.set_child(Some(&picture));
+    button.set_child(Some(&video));
     button.connect_clicked(move |_| on_click());
 
     button
 }
+
+/// Wire a long-press and secondary-click `GtkPopoverMenu` onto a strip
+/// thumbnail, offering Pin/Unpin, Edit, Mark for Print, and Delete. Pin/Unpin is
+/// applied immediately (badge class + `pinned` set, so the next
+/// `update_photos` sorts it to the front); the other three actions are purely
+/// forwarded to `photo_action_callback` for the caller to act on.
+fn build_photo_context_menu(
+    button: &gtk::Button,
+    photo_id: String,
+    pinned: &Rc<RefCell<HashSet<String>>>,
+    photo_action_callback: &Rc<RefCell<Option<Box<dyn Fn(&str, PhotoAction)>>>>,
+) {
+    if pinned.borrow().contains(&photo_id) {
+        button.add_css_class("pinned");
+    }
+
+    let menu = gtk::gio::Menu::new();
+    menu.append(
+        Some(if pinned.borrow().contains(&photo_id) {
+            "Unpin"
+        } else {
+            "Pin"
+        }),
+        Some("photo.pin"),
+    );
+    menu.append(Some("Edit"), Some("photo.edit"));
+    menu.append(Some("Mark for Print"), Some("photo.print"));
+    menu.append(Some("Delete"), Some("photo.delete"));
+
+    let actions = gtk::gio::SimpleActionGroup::new();
+
+    let pin_action = gtk::gio::SimpleAction::new("pin", None);
+    {
+        let button = button.clone();
+        let photo_id = photo_id.clone();
+        let pinned = pinned.clone();
+        let photo_action_callback = photo_action_callback.clone();
+        pin_action.connect_activate(move |_, _| {
+            let now_pinned = !pinned.borrow().contains(&photo_id);
+            if now_pinned {
+                pinned.borrow_mut().insert(photo_id.clone());
+                button.add_css_class("pinned");
+            } else {
+                pinned.borrow_mut().remove(&photo_id);
+                button.remove_css_class("pinned");
+            }
+            if let Some(callback) = photo_action_callback.borrow().as_ref() {
+                let action = if now_pinned {
+                    PhotoAction::Pin
+                } else {
+                    PhotoAction::Unpin
+                };
+                callback(&photo_id, action);
+            }
+        });
+    }
+    actions.add_action(&pin_action);
+
+    let edit_action = gtk::gio::SimpleAction::new("edit", None);
+    {
+        let photo_id = photo_id.clone();
+        let photo_action_callback = photo_action_callback.clone();
+        edit_action.connect_activate(move |_, _| {
+            if let Some(callback) = photo_action_callback.borrow().as_ref() {
+                callback(&photo_id, PhotoAction::Edit);
+            }
+        });
+    }
+    actions.add_action(&edit_action);
+
+    let print_action = gtk::gio::SimpleAction::new("print", None);
+    {
+        let photo_id = photo_id.clone();
+        let photo_action_callback = photo_action_callback.clone();
+        print_action.connect_activate(move |_, _| {
+            if let Some(callback) = photo_action_callback.borrow().as_ref() {
+                callback(&photo_id, PhotoAction::MarkForPrint);
+            }
+        });
+    }
+    actions.add_action(&print_action);
+
+    let delete_action = gtk::gio::SimpleAction::new("delete", None);
+    {
+        let photo_id = photo_id.clone();
+        let photo_action_callback = photo_action_callback.clone();
+        delete_action.connect_activate(move |_, _| {
+            if let Some(callback) = photo_action_callback.borrow().as_ref() {
+                callback(&photo_id, PhotoAction::Delete);
+            }
+        });
+    }
+    actions.add_action(&delete_action);
+
+    button.insert_action_group("photo", Some(&actions));
+
+    let popover = gtk::PopoverMenu::from_model(Some(&menu));
+    popover.set_parent(button);
+
+    let long_press = gtk::GestureLongPress::new();
+    let popover_for_long_press = popover.clone();
+    long_press.connect_pressed(move |_, _, _| {
+        popover_for_long_press.popup();
+    });
+    button.add_controller(long_press);
+
+    let secondary_click = gtk::GestureClick::new();
+    secondary_click.set_button(gtk::gdk::BUTTON_SECONDARY);
+    secondary_click.connect_pressed(move |_, _, _, _| {
+        popover.popup();
+    });
+    button.add_controller(secondary_click);
+}
+
+/// Play only the clip previews currently scrolled into the photo strip's
+/// viewport, pausing the rest - called whenever the strip scrolls, a clip
+/// finishes loading, or `previews_active` itself changes.
+fn update_clip_visibility(
+    photo_strip: &gtk::ScrolledWindow,
+    clip_previews: &Rc<RefCell<Vec<gtk::Video>>>,
+    previews_active: &Rc<Cell<bool>>,
+) {
+    if !previews_active.get() {
+        for video in clip_previews.borrow().iter() {
+            video.pause();
+        }
+        return;
+    }
+
+    let viewport_width = photo_strip.width() as f32;
+
+    for video in clip_previews.borrow().iter() {
+        let visible = video
+            .compute_bounds(photo_strip)
+            .map(|bounds| bounds.x() + bounds.width() > 0.0 && bounds.x() < viewport_width)
+            .unwrap_or(false);
+
+        if visible {
+            video.play();
+        } else {
+            video.pause();
+        }
+    }
+}
+
+/// Open the media viewer on `current_photos[index]`, updating
+/// `current_selection` so the strip highlight follows. A no-op if the index
+/// is out of range (e.g. the underlying photo was removed).
+fn media_viewer_show(
+    ctx: &Rc<AppContext>,
+    media_viewer: &MediaViewerWidgets,
+    current_photos: &Rc<RefCell<Vec<PhotoInfo>>>,
+    current_selection: &Rc<RefCell<Option<usize>>>,
+    index: usize,
+) {
+    let photo = match current_photos.borrow().get(index) {
+        Some(photo) => photo.clone(),
+        None => return,
+    };
+
+    *current_selection.borrow_mut() = Some(index);
+
+    media_viewer.bin.set_scale(VIEWER_MIN_ZOOM_SCALE);
+    media_viewer.bin.set_translate(0.0, 0.0);
+
+    match photo.kind {
+        MediaKind::Image => {
+            media_viewer.video.pause();
+            media_viewer.video.set_media_stream(None::<&gtk::MediaFile>);
+            media_viewer.media_stack.set_visible_child_name("image");
+            load_viewer_image(ctx, &photo.web_url, &media_viewer.picture);
+        }
+        MediaKind::Clip => {
+            media_viewer.media_stack.set_visible_child_name("video");
+            load_viewer_clip(ctx, &photo.id, &photo.web_url, &media_viewer.video);
+        }
+    }
+
+    if !media_viewer.overlay.is_visible() {
+        media_viewer.overlay.set_opacity(0.0);
+        media_viewer.overlay.set_visible(true);
+        animations::fade_in(&media_viewer.overlay, animations::duration::FAST);
+    }
+}
+
+/// Close the media viewer, pausing any playing clip first.
+fn media_viewer_hide(media_viewer: &MediaViewerWidgets) {
+    media_viewer.video.pause();
+    animations::fade_out(&media_viewer.overlay, animations::duration::FAST);
+}
+
+/// Move the media viewer by `delta` positions (e.g. -1/+1 for prev/next),
+/// stopping at either end of `current_photos` rather than wrapping.
+fn media_viewer_navigate(
+    ctx: &Rc<AppContext>,
+    media_viewer: &MediaViewerWidgets,
+    current_photos: &Rc<RefCell<Vec<PhotoInfo>>>,
+    current_selection: &Rc<RefCell<Option<usize>>>,
+    delta: i32,
+) {
+    let count = current_photos.borrow().len();
+    let current = current_selection.borrow().unwrap_or(0) as i32;
+    let next = current + delta;
+
+    if next >= 0 && (next as usize) < count {
+        media_viewer_show(
+            ctx,
+            media_viewer,
+            current_photos,
+            current_selection,
+            next as usize,
+        );
+    }
+}
+
+/// Load an image into the media viewer's picture widget.
+fn load_viewer_image(ctx: &Rc<AppContext>, url: &str, picture: &gtk::Picture) {
+    let full_url = config::photo_url(url);
+    picture.set_paintable(None::<&gtk::gdk::Paintable>);
+    picture.set_opacity(0.0);
+
+    let picture = picture.clone();
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime.spawn(async move { api.fetch_image(&full_url).await }).await;
+
+        match result {
+            Ok(Ok(bytes)) => {
+                let gbytes = glib::Bytes::from(&bytes);
+                let stream = gtk::gio::MemoryInputStream::from_bytes(&gbytes);
+                if let Ok(pixbuf) = gtk::gdk_pixbuf::Pixbuf::from_stream(
+                    &stream,
+                    None::<&gtk::gio::Cancellable>,
+                ) {
+                    let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
+                    picture.set_paintable(Some(&texture));
+                    animations::fade_in(&picture, animations::duration::FAST);
+                }
+            }
+            Ok(Err(e)) => log::error!("Failed to load viewer image: {}", e),
+            Err(e) => log::error!("Task join error: {}", e),
+        }
+    });
+}
+
+/// Load a boomerang clip into the media viewer's video widget, muted playback
+/// not required here since the viewer is a deliberate full-attention view.
+fn load_viewer_clip(ctx: &Rc<AppContext>, clip_id: &str, url: &str, video: &gtk::Video) {
+    let full_url = config::photo_url(url);
+    let ext = Path::new(url)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_string();
+    let temp_path = std::env::temp_dir().join(format!("picpop-viewer-clip-{}.{}", clip_id, ext));
+    let video = video.clone();
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime.spawn(async move { api.fetch_image(&full_url).await }).await;
+
+        match result {
+            Ok(Ok(bytes)) => {
+                if let Err(e) = std::fs::write(&temp_path, &bytes) {
+                    log::error!("Failed to write viewer clip to temp file: {}", e);
+                    return;
+                }
+                let media_file = gtk::MediaFile::for_filename(&temp_path);
+                media_file.set_loop(true);
+                video.set_media_stream(Some(&media_file));
+                media_file.play();
+            }
+            Ok(Err(e)) => log::error!("Failed to load viewer clip: {}", e),
+            Err(e) => log::error!("Task join error: {}", e),
+        }
+    });
+}
@@ -2,17 +2,32 @@
 
 use gtk4 as gtk;
 use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use crate::api::{MediaKind, PhotoInfo};
 use crate::app::AppContext;
+use crate::config;
+use crate::ui::widgets::{animations, ScaleBin, ScannerPanel};
 
-/// Create the welcome screen
+/// How long each attract-mode slide is shown before crossfading to the next,
+/// in milliseconds
+const ATTRACT_SLIDE_DURATION_MS: u32 = 6000;
+/// Crossfade duration between attract-mode slides, in milliseconds
+const ATTRACT_CROSSFADE_MS: u32 = 1200;
+/// Ken-Burns zoom applied to the visible slide over its full display
+/// duration (1.0 -> this)
+const ATTRACT_ZOOM_TARGET: f64 = 1.08;
+
+/// Create the welcome screen, along with the attract-mode slideshow overlaid
+/// on top of it. The caller hangs on to the slideshow handle and passes it
+/// back to `set_attract_mode` to start/stop the loop.
 pub fn create_welcome_screen(
-    _ctx: &Rc<AppContext>,
+    ctx: &Rc<AppContext>,
     video_paintable: &gtk::gdk::Paintable,
     is_loading: bool,
     on_start: impl Fn() + 'static,
-) -> gtk::Overlay {
+) -> (gtk::Overlay, Rc<AttractSlideshow>) {
     let overlay = gtk::Overlay::new();
     overlay.add_css_class("welcome-screen");
 
@@ -26,6 +41,11 @@ pub fn create_welcome_screen(
 
     overlay.set_child(Some(&video));
 
+    // Attract-mode photo slideshow, shown over the video background once the
+    // kiosk has been idle long enough - see `set_attract_mode`
+    let slideshow = AttractSlideshow::new();
+    overlay.add_overlay(slideshow.widget());
+
     // Center content overlay
     let center_box = gtk::Box::new(gtk::Orientation::Vertical, 24);
     center_box.set_halign(gtk::Align::Center);
@@ -41,6 +61,20 @@ pub fn create_welcome_screen(
     icon_label.add_css_class("welcome-icon-emoji");
     icon_frame.set_child(Some(&icon_label));
 
+    // Long-pressing the logo opens the kiosk pairing/setup scanner. This
+    // only controls discoverability, not authorization - a scanned
+    // `PICPOP-CFG:` payload still needs the operator pin configured as
+    // `setup_pin` before `AppContext::apply_scanned_config` persists
+    // anything, since anyone who can touch the screen can find the
+    // long-press.
+    let setup_panel = create_setup_panel(ctx);
+    let open_setup = setup_panel.clone();
+    let long_press = gtk::GestureLongPress::new();
+    long_press.connect_pressed(move |_, _, _| {
+        open_setup.set_visible(true);
+    });
+    icon_frame.add_controller(long_press);
+
     // Title
     let title = gtk::Label::new(Some("PicPop"));
     title.add_css_class("welcome-title");
@@ -73,8 +107,84 @@ pub fn create_welcome_screen(
     center_box.append(&button);
 
     overlay.add_overlay(&center_box);
+    overlay.add_overlay(setup_panel.widget());
+
+    (overlay, slideshow)
+}
+
+/// Build the hidden-by-default kiosk pairing/setup panel: a full-screen
+/// scrim over the `ScannerPanel`'s camera preview, with a close button.
+/// Kept unmapped (and so camera-free, per `ScannerPanel`'s own
+/// map/unmap-gated pipeline) until a long-press on the welcome logo reveals
+/// it.
+fn create_setup_panel(ctx: &Rc<AppContext>) -> Rc<SetupPanel> {
+    let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    container.add_css_class("setup-panel");
+    container.set_hexpand(true);
+    container.set_vexpand(true);
+    container.set_visible(false);
+
+    let header = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    header.set_halign(gtk::Align::Fill);
+
+    let title = gtk::Label::new(Some("Scan to pair or configure"));
+    title.add_css_class("setup-panel-title");
+    title.set_hexpand(true);
+    header.append(&title);
 
-    overlay
+    let close_button = gtk::Button::from_icon_name("window-close-symbolic");
+    close_button.add_css_class("setup-panel-close");
+    header.append(&close_button);
+
+    container.append(&header);
+
+    let scanner = ScannerPanel::new(ctx);
+    container.append(scanner.widget());
+
+    let panel = Rc::new(SetupPanel { container });
+
+    let close_panel = panel.clone();
+    close_button.connect_clicked(move |_| close_panel.set_visible(false));
+
+    panel
+}
+
+/// Handle to the welcome screen's pairing/setup overlay.
+struct SetupPanel {
+    container: gtk::Box,
+}
+
+impl SetupPanel {
+    fn widget(&self) -> &gtk::Box {
+        &self.container
+    }
+
+    fn set_visible(&self, visible: bool) {
+        self.container.set_visible(visible);
+    }
+}
+
+/// Toggle the welcome screen's attract-mode slideshow: starts (or stops) the
+/// looping crossfade over `photos` and applies the CSS class the stylesheet
+/// uses to dim the rest of the screen while it plays.
+pub fn set_attract_mode(
+    screen: &gtk::Overlay,
+    slideshow: &Rc<AttractSlideshow>,
+    ctx: &Rc<AppContext>,
+    active: bool,
+    photos: Vec<PhotoInfo>,
+) {
+    if active {
+        screen.add_css_class("attract-mode");
+    } else {
+        screen.remove_css_class("attract-mode");
+    }
+
+    if active {
+        slideshow.start(ctx, photos);
+    } else {
+        slideshow.stop();
+    }
 }
 
 /// Update the start button state
@@ -105,3 +215,189 @@ pub fn update_start_button(screen: &gtk::Overlay, is_loading: bool, error: Optio
         child = widget.next_sibling();
     }
 }
+
+/// Looping, muted slideshow of recent session photos, crossfaded with a slow
+/// Ken-Burns zoom over a repeating `glib::timeout_add_local` timer. Built
+/// once alongside the welcome screen (hidden, with no photos loaded) and
+/// driven entirely by `set_attract_mode`, which the state machine's
+/// `attract_mode_active`/`StartAttractLoop`/`StopAttractLoop` flow calls into.
+pub struct AttractSlideshow {
+    container: gtk::Overlay,
+    front: ScaleBin,
+    back: ScaleBin,
+    front_picture: gtk::Picture,
+    back_picture: gtk::Picture,
+    front_visible: Cell<bool>,
+    photos: RefCell<Vec<PhotoInfo>>,
+    index: Cell<usize>,
+    timeout_id: RefCell<Option<glib::SourceId>>,
+}
+
+impl AttractSlideshow {
+    /// Build an empty, hidden slideshow; `start` loads its first photo.
+    pub fn new() -> Rc<Self> {
+        let container = gtk::Overlay::new();
+        container.add_css_class("attract-slideshow");
+        container.set_hexpand(true);
+        container.set_vexpand(true);
+        container.set_visible(false);
+
+        let front_picture = gtk::Picture::new();
+        front_picture.set_content_fit(gtk::ContentFit::Cover);
+        let front = ScaleBin::new(&front_picture);
+        front.set_hexpand(true);
+        front.set_vexpand(true);
+
+        let back_picture = gtk::Picture::new();
+        back_picture.set_content_fit(gtk::ContentFit::Cover);
+        let back = ScaleBin::new(&back_picture);
+        back.set_hexpand(true);
+        back.set_vexpand(true);
+        back.set_opacity(0.0);
+
+        container.set_child(Some(&front));
+        container.add_overlay(&back);
+
+        Rc::new(Self {
+            container,
+            front,
+            back,
+            front_picture,
+            back_picture,
+            front_visible: Cell::new(true),
+            photos: RefCell::new(Vec::new()),
+            index: Cell::new(0),
+            timeout_id: RefCell::new(None),
+        })
+    }
+
+    pub fn widget(&self) -> &gtk::Overlay {
+        &self.container
+    }
+
+    /// Start (or restart) the loop over `photos` - still images only, clips
+    /// are skipped since `Picture` can't play them back. A no-op if that
+    /// leaves nothing to show.
+    pub fn start(self: &Rc<Self>, ctx: &Rc<AppContext>, photos: Vec<PhotoInfo>) {
+        self.stop();
+
+        let photos: Vec<PhotoInfo> = photos
+            .into_iter()
+            .filter(|p| p.kind == MediaKind::Image)
+            .collect();
+        if photos.is_empty() {
+            return;
+        }
+
+        self.index.set(0);
+        self.front_visible.set(true);
+        self.front.set_opacity(1.0);
+        self.front.set_scale(1.0);
+        self.back.set_opacity(0.0);
+        self.back.set_scale(1.0);
+
+        load_attract_image(ctx, &photos[0].web_url, &self.front_picture);
+        self.animate_zoom(&self.front);
+        *self.photos.borrow_mut() = photos;
+
+        self.container.set_visible(true);
+        self.schedule_next(ctx);
+    }
+
+    /// Stop the loop and hide the slideshow, e.g. once attract mode ends.
+    pub fn stop(&self) {
+        if let Some(id) = self.timeout_id.borrow_mut().take() {
+            id.remove();
+        }
+        self.container.set_visible(false);
+    }
+
+    fn schedule_next(self: &Rc<Self>, ctx: &Rc<AppContext>) {
+        let this = self.clone();
+        let ctx = ctx.clone();
+        let id = glib::timeout_add_local(
+            std::time::Duration::from_millis(ATTRACT_SLIDE_DURATION_MS as u64),
+            move || {
+                this.advance(&ctx);
+                glib::ControlFlow::Continue
+            },
+        );
+        *self.timeout_id.borrow_mut() = Some(id);
+    }
+
+    /// Crossfade from the currently-visible picture to the next photo in the
+    /// loop, restarting the Ken-Burns zoom on the incoming one.
+    fn advance(self: &Rc<Self>, ctx: &Rc<AppContext>) {
+        let next_index = {
+            let photos = self.photos.borrow();
+            if photos.is_empty() {
+                return;
+            }
+            (self.index.get() + 1) % photos.len()
+        };
+        self.index.set(next_index);
+
+        let front_visible = self.front_visible.get();
+        let (showing, hiding_in, hiding_in_picture) = if front_visible {
+            (&self.front, &self.back, &self.back_picture)
+        } else {
+            (&self.back, &self.front, &self.front_picture)
+        };
+
+        let url = self.photos.borrow()[next_index].web_url.clone();
+        load_attract_image(ctx, &url, hiding_in_picture);
+        hiding_in.set_scale(1.0);
+        self.animate_zoom(hiding_in);
+
+        animations::fade(showing, 1.0, 0.0, ATTRACT_CROSSFADE_MS, None);
+        animations::fade(hiding_in, 0.0, 1.0, ATTRACT_CROSSFADE_MS, None);
+        self.front_visible.set(!front_visible);
+    }
+
+    /// Slowly zoom `bin` from 1.0 to `ATTRACT_ZOOM_TARGET` over the slide's
+    /// full display duration, for the Ken-Burns effect.
+    fn animate_zoom(&self, bin: &ScaleBin) {
+        animations::scale_reveal(
+            bin,
+            (1.0, 0.0, 0.0),
+            (ATTRACT_ZOOM_TARGET, 0.0, 0.0),
+            ATTRACT_SLIDE_DURATION_MS,
+            None,
+        );
+    }
+}
+
+/// Fetch and decode a photo at full resolution into `picture`, reusing the
+/// photo strip's thumbnail fetch path but against `web_url` instead of
+/// `thumbnail_url`.
+fn load_attract_image(ctx: &Rc<AppContext>, web_url: &str, picture: &gtk::Picture) {
+    let url = config::photo_url(web_url);
+    let picture = picture.clone();
+    let api = ctx.api.clone();
+    let runtime = ctx.runtime.clone();
+
+    glib::spawn_future_local(async move {
+        let result = runtime
+            .spawn(async move { api.fetch_image(&url).await })
+            .await;
+
+        match result {
+            Ok(Ok(bytes)) => {
+                let gbytes = glib::Bytes::from(&bytes);
+                let stream = gtk::gio::MemoryInputStream::from_bytes(&gbytes);
+                if let Ok(pixbuf) =
+                    gtk::gdk_pixbuf::Pixbuf::from_stream(&stream, None::<&gtk::gio::Cancellable>)
+                {
+                    let texture = gtk::gdk::Texture::for_pixbuf(&pixbuf);
+                    picture.set_paintable(Some(&texture));
+                }
+            }
+            Ok(Err(e)) => {
+                log::error!("Failed to load attract-mode image: {}", e);
+            }
+            Err(e) => {
+                log::error!("Task join error: {}", e);
+            }
+        }
+    });
+}
@@ -10,6 +10,9 @@ use libadwaita as adw;
 use libadwaita::prelude::*;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use super::scale_bin::ScaleBin;
 
 /// Animation durations (in milliseconds)
 pub mod duration {
@@ -72,29 +75,72 @@ pub fn fade_out(widget: &impl IsA<gtk::Widget>, duration_ms: u32) -> adw::TimedA
     )
 }
 
-/// Animate widget scale using CSS transform
-/// Note: Requires the widget to support CSS transforms
-pub fn scale_bounce(widget: &impl IsA<gtk::Widget>, duration_ms: u32) -> adw::TimedAnimation {
-    let widget_clone = widget.clone().upcast::<gtk::Widget>();
+/// Piecewise-linear interpolation of `value` between the breakpoints in
+/// `input_range`, mapped onto the matching points in `output_range`.
+/// Clamps to the first/last output when `value` falls outside the range.
+///
+/// `input_range` and `output_range` must be the same length and have at
+/// least two points; `input_range` must be sorted ascending.
+pub fn interpolate(value: f64, input_range: &[f64], output_range: &[f64]) -> f64 {
+    assert_eq!(
+        input_range.len(),
+        output_range.len(),
+        "interpolate: input_range and output_range must be the same length"
+    );
+    assert!(
+        input_range.len() >= 2,
+        "interpolate: need at least two breakpoints"
+    );
 
-    // Scale down then back up (0 -> 0.5 -> 1.0 maps to 1.0 -> 0.9 -> 1.0)
-    let target = adw::CallbackAnimationTarget::new(move |value| {
-        // value goes 0 -> 1, we want scale: 1.0 -> 0.9 -> 1.0
-        let scale = if value < 0.5 {
-            1.0 - (value * 0.2) // 1.0 -> 0.9
-        } else {
-            0.9 + ((value - 0.5) * 0.2) // 0.9 -> 1.0
-        };
-        // Apply scale via CSS class or direct style
-        // GTK4 doesn't have direct scale, so we use size hints
-        let current_width = widget_clone.width();
-        let current_height = widget_clone.height();
-        if current_width > 0 && current_height > 0 {
-            widget_clone.set_size_request(
-                (current_width as f64 * scale) as i32,
-                (current_height as f64 * scale) as i32,
-            );
+    if value <= input_range[0] {
+        return output_range[0];
+    }
+    let last = input_range.len() - 1;
+    if value >= input_range[last] {
+        return output_range[last];
+    }
+
+    for i in 0..last {
+        let (x0, x1) = (input_range[i], input_range[i + 1]);
+        if value >= x0 && value <= x1 {
+            let (y0, y1) = (output_range[i], output_range[i + 1]);
+            let t = (value - x0) / (x1 - x0);
+            return y0 + t * (y1 - y0);
         }
+    }
+
+    output_range[last]
+}
+
+/// Build a `CallbackAnimationTarget` that maps the animation's `0.0..1.0`
+/// progress through `interpolate` across `keyframes` (pairs of
+/// `(position, value)`) before handing the result to `apply`.
+pub fn keyframed_target(
+    keyframes: &[(f64, f64)],
+    apply: impl Fn(f64) + 'static,
+) -> adw::CallbackAnimationTarget {
+    let input_range: Vec<f64> = keyframes.iter().map(|(x, _)| *x).collect();
+    let output_range: Vec<f64> = keyframes.iter().map(|(_, y)| *y).collect();
+
+    adw::CallbackAnimationTarget::new(move |value| {
+        apply(interpolate(value, &input_range, &output_range));
+    })
+}
+
+/// Animate a widget "popping" from scale 1.0 -> 0.9 -> 1.0.
+///
+/// `widget` must be (or be castable to) a [`ScaleBin`], whose `snapshot`
+/// applies the scale as a `gsk::Transform` at paint time, so neighboring
+/// widgets never see a relayout.
+pub fn scale_bounce(widget: &impl IsA<gtk::Widget>, duration_ms: u32) -> adw::TimedAnimation {
+    let bin = widget
+        .clone()
+        .upcast::<gtk::Widget>()
+        .downcast::<ScaleBin>()
+        .expect("scale_bounce requires a ScaleBin widget");
+
+    let target = keyframed_target(&[(0.0, 1.0), (0.5, 0.9), (1.0, 1.0)], move |scale| {
+        bin.set_scale(scale);
     });
 
     let animation = adw::TimedAnimation::builder()
@@ -110,19 +156,109 @@ pub fn scale_bounce(widget: &impl IsA<gtk::Widget>, duration_ms: u32) -> adw::Ti
     animation
 }
 
+/// Animate a [`ScaleBin`] between two (scale, translate_x, translate_y)
+/// states, for a thumbnail <-> fullscreen "reveal" transition (e.g. the
+/// lightbox opening from a tapped photo and closing back into it).
+///
+/// `from`/`to` are each `(scale, translate_x, translate_y)`, with the
+/// translation in pixels. Calling this to open the lightbox passes the
+/// thumbnail-sized state as `from` and `(1.0, 0.0, 0.0)` as `to`; closing
+/// swaps them.
+pub fn scale_reveal(
+    widget: &impl IsA<gtk::Widget>,
+    from: (f64, f64, f64),
+    to: (f64, f64, f64),
+    duration_ms: u32,
+    on_complete: Option<Box<dyn Fn()>>,
+) -> adw::TimedAnimation {
+    let bin = widget
+        .clone()
+        .upcast::<gtk::Widget>()
+        .downcast::<ScaleBin>()
+        .expect("scale_reveal requires a ScaleBin widget");
+
+    bin.set_scale(from.0);
+    bin.set_translate(from.1, from.2);
+
+    let target = adw::CallbackAnimationTarget::new(move |value| {
+        let scale = interpolate(value, &[0.0, 1.0], &[from.0, to.0]);
+        let tx = interpolate(value, &[0.0, 1.0], &[from.1, to.1]);
+        let ty = interpolate(value, &[0.0, 1.0], &[from.2, to.2]);
+        bin.set_scale(scale);
+        bin.set_translate(tx, ty);
+    });
+
+    let animation = adw::TimedAnimation::builder()
+        .widget(widget)
+        .value_from(0.0)
+        .value_to(1.0)
+        .duration(duration_ms)
+        .easing(adw::Easing::EaseOutCubic)
+        .target(&target)
+        .build();
+
+    if let Some(callback) = on_complete {
+        animation.connect_done(move |_| callback());
+    }
+
+    animation.play();
+    animation
+}
+
 /// Countdown animation - scale down with fade for dramatic effect
+#[derive(Clone)]
 pub struct CountdownAnimator {
     label: gtk::Label,
     overlay: gtk::Box,
     animation: Rc<RefCell<Option<adw::TimedAnimation>>>,
+    timer: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+/// Largest font size (in points) `animate_number` scales a digit up to
+/// (120% of the base 200pt size), used to reserve the label's allocation.
+const COUNTDOWN_MAX_FONT_SIZE_PT: i32 = 240;
+
+/// Reserve a fixed allocation on `label`, sized from the *ink* extents (not
+/// the logical extents) of the widest single digit 0-9 at `max_font_size_pt`.
+///
+/// Logical extents include font leading/trailing whitespace and vary less
+/// between glyphs than their actual ink, which is why sizing off them lets
+/// `animate_number` clip the biggest digit, or shift "1" sideways relative
+/// to "3" as the label re-centers each frame. Sizing and centering off ink
+/// bounds instead fixes both.
+fn reserve_countdown_extent(label: &gtk::Label, max_font_size_pt: i32) {
+    let mut font_desc = label
+        .pango_context()
+        .font_description()
+        .unwrap_or_else(gtk::pango::FontDescription::new);
+    font_desc.set_weight(gtk::pango::Weight::Bold);
+    font_desc.set_size(max_font_size_pt * gtk::pango::SCALE);
+
+    let mut max_width = 0;
+    let mut max_height = 0;
+
+    for digit in 0..=9 {
+        let layout = label.create_pango_layout(Some(&digit.to_string()));
+        layout.set_font_description(Some(&font_desc));
+        let (ink, _logical) = layout.pixel_extents();
+        max_width = max_width.max(ink.width());
+        max_height = max_height.max(ink.height());
+    }
+
+    label.set_size_request(max_width, max_height);
+    label.set_halign(gtk::Align::Center);
+    label.set_valign(gtk::Align::Center);
 }
 
 impl CountdownAnimator {
     pub fn new(label: gtk::Label, overlay: gtk::Box) -> Self {
+        reserve_countdown_extent(&label, COUNTDOWN_MAX_FONT_SIZE_PT);
+
         Self {
             label,
             overlay,
             animation: Rc::new(RefCell::new(None)),
+            timer: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -162,13 +298,13 @@ impl CountdownAnimator {
 
         // Animate opacity and we'll use CSS for the scale effect
         let target = adw::CallbackAnimationTarget::new(move |value| {
-            // Opacity: 0 -> 1 in first half, stay at 1
-            let opacity = if value < 0.3 { value / 0.3 } else { 1.0 };
+            // Opacity: 0 -> 1 over the first 30%, then stays at 1
+            let opacity = interpolate(value, &[0.0, 0.3, 1.0], &[0.0, 1.0, 1.0]);
             label.set_opacity(opacity);
 
             // Scale effect via font size (countdown numbers are large)
             // Start at 120% size, end at 100%
-            let scale = 1.2 - (value * 0.2);
+            let scale = interpolate(value, &[0.0, 1.0], &[1.2, 1.0]);
             let base_size = 200; // Base font size in px
             let font_size = (base_size as f64 * scale) as i32;
 
@@ -192,24 +328,175 @@ impl CountdownAnimator {
         animation.play();
         *self.animation.borrow_mut() = Some(animation);
     }
+
+    /// Drive a full countdown automatically: ticks once every `interval_ms`,
+    /// calling `animate_number` with each decremented value so the digits
+    /// count down "3…2…1" on their own, then fades the whole label out
+    /// (reusing `fade` at `duration::FAST`) before invoking `on_complete` —
+    /// the photo-booth capture path wires this to the shutter.
+    ///
+    /// A second call cancels the previous timer and any in-flight
+    /// `TimedAnimation` before starting the new sequence.
+    pub fn start_sequence(&self, from: i32, interval_ms: u32, on_complete: Box<dyn Fn()>) {
+        if let Some(source_id) = self.timer.borrow_mut().take() {
+            source_id.remove();
+        }
+        if let Some(ref anim) = *self.animation.borrow() {
+            anim.skip();
+        }
+
+        self.animate_number(from);
+
+        let remaining = Rc::new(RefCell::new(from));
+        let animator = self.clone();
+        let on_complete = Rc::new(on_complete);
+
+        let source_id =
+            glib::timeout_add_local(Duration::from_millis(interval_ms as u64), move || {
+                let mut remaining = remaining.borrow_mut();
+                *remaining -= 1;
+
+                if *remaining > 0 {
+                    animator.animate_number(*remaining);
+                    glib::ControlFlow::Continue
+                } else {
+                    *animator.timer.borrow_mut() = None;
+                    let on_complete = on_complete.clone();
+                    fade(
+                        &animator.overlay,
+                        1.0,
+                        0.0,
+                        duration::FAST,
+                        Some(Box::new(move || on_complete())),
+                    );
+                    glib::ControlFlow::Break
+                }
+            });
+
+        *self.timer.borrow_mut() = Some(source_id);
+    }
+}
+
+/// Result of a [`CountdownState::tick`] call.
+pub enum CountdownTick {
+    /// Time still left before the deadline.
+    Remaining(Duration),
+    /// The deadline has passed; the countdown is done.
+    Complete,
+}
+
+/// Pause/resume-aware countdown clock, driven by a per-frame [`CountdownState::tick`]
+/// call rather than a one-shot timer, so the visible number (and any throb
+/// animation) stays in sync with real elapsed time even when the booth
+/// operator freezes and resumes the countdown mid-sequence.
+#[derive(Debug, Clone, Copy)]
+pub enum CountdownState {
+    /// Counting down; `deadline` is when it completes.
+    Running {
+        last_update: Instant,
+        deadline: Instant,
+    },
+    /// Frozen, with `time_remaining` left to run once resumed.
+    Paused { time_remaining: Duration },
+}
+
+impl CountdownState {
+    /// Create a new, paused countdown with `time_remaining` left to run.
+    pub fn new(time_remaining: Duration) -> Self {
+        CountdownState::Paused { time_remaining }
+    }
+
+    /// Paused -> Running: resume from `time_remaining`, starting the deadline now.
+    pub fn start(&mut self) {
+        if let CountdownState::Paused { time_remaining } = *self {
+            let now = Instant::now();
+            *self = CountdownState::Running {
+                last_update: now,
+                deadline: now + time_remaining,
+            };
+        }
+    }
+
+    /// Running -> Paused: freeze with whatever time is left before the deadline.
+    pub fn pause(&mut self) {
+        if let CountdownState::Running { deadline, .. } = *self {
+            *self = CountdownState::Paused {
+                time_remaining: deadline.saturating_duration_since(Instant::now()),
+            };
+        }
+    }
+
+    /// Pause if running, resume if paused.
+    pub fn toggle(&mut self) {
+        match self {
+            CountdownState::Running { .. } => self.pause(),
+            CountdownState::Paused { .. } => self.start(),
+        }
+    }
+
+    /// Advance the clock to `now`, updating `last_update` while running and
+    /// reporting the time left, or [`CountdownTick::Complete`] once `now`
+    /// passes the deadline.
+    pub fn tick(&mut self, now: Instant) -> CountdownTick {
+        match *self {
+            CountdownState::Running { deadline, .. } => {
+                if now > deadline {
+                    CountdownTick::Complete
+                } else {
+                    *self = CountdownState::Running {
+                        last_update: now,
+                        deadline,
+                    };
+                    CountdownTick::Remaining(deadline - now)
+                }
+            }
+            CountdownState::Paused { time_remaining } => CountdownTick::Remaining(time_remaining),
+        }
+    }
+
+    /// Seconds left before the deadline, for refreshing the overlay label.
+    pub fn remaining_seconds(&self) -> f64 {
+        match *self {
+            CountdownState::Running { deadline, .. } => deadline
+                .saturating_duration_since(Instant::now())
+                .as_secs_f64(),
+            CountdownState::Paused { time_remaining } => time_remaining.as_secs_f64(),
+        }
+    }
 }
 
 /// Animate a widget sliding in from a direction
 pub fn slide_in_from_right(
     widget: &impl IsA<gtk::Widget>,
     duration_ms: u32,
+) -> adw::TimedAnimation {
+    slide_in_from_right_with_distance(widget, duration_ms, 50)
+}
+
+/// Like `slide_in_from_right`, but picks its travel distance automatically
+/// from the widget's own width and its monitor's geometry (via
+/// `scaled_slide_distance`), instead of a hardcoded 50px, so the slide
+/// covers a sensible distance on both a 1080p kiosk and a 4K wall display.
+pub fn slide_in_from_right_scaled(
+    widget: &impl IsA<gtk::Widget>,
+    duration_ms: u32,
+) -> adw::TimedAnimation {
+    let distance = scaled_slide_distance(widget, widget.clone().upcast::<gtk::Widget>().width());
+    slide_in_from_right_with_distance(widget, duration_ms, distance)
+}
+
+fn slide_in_from_right_with_distance(
+    widget: &impl IsA<gtk::Widget>,
+    duration_ms: u32,
+    start_margin: i32,
 ) -> adw::TimedAnimation {
     widget.set_visible(true);
     widget.set_opacity(0.0);
 
     let widget_clone = widget.clone().upcast::<gtk::Widget>();
-    let start_margin = 50; // Start 50px to the right
-
-    // Get current margin
-    let _original_margin = widget_clone.margin_end();
 
     let target = adw::CallbackAnimationTarget::new(move |value| {
-        // Slide: margin goes from +50 to 0
+        // Slide: margin goes from start_margin to 0
         let offset = ((1.0 - value) * start_margin as f64) as i32;
         widget_clone.set_margin_start(offset);
 
@@ -239,12 +526,30 @@ pub fn slide_in_from_right(
 pub fn slide_in_from_bottom(
     widget: &impl IsA<gtk::Widget>,
     duration_ms: u32,
+) -> adw::TimedAnimation {
+    slide_in_from_bottom_with_distance(widget, duration_ms, 30)
+}
+
+/// Like `slide_in_from_bottom`, but picks its travel distance automatically
+/// from the widget's own height and its monitor's geometry (via
+/// `scaled_slide_distance`), instead of a hardcoded 30px.
+pub fn slide_in_from_bottom_scaled(
+    widget: &impl IsA<gtk::Widget>,
+    duration_ms: u32,
+) -> adw::TimedAnimation {
+    let distance = scaled_slide_distance(widget, widget.clone().upcast::<gtk::Widget>().height());
+    slide_in_from_bottom_with_distance(widget, duration_ms, distance)
+}
+
+fn slide_in_from_bottom_with_distance(
+    widget: &impl IsA<gtk::Widget>,
+    duration_ms: u32,
+    start_offset: i32,
 ) -> adw::TimedAnimation {
     widget.set_visible(true);
     widget.set_opacity(0.0);
 
     let widget_clone = widget.clone().upcast::<gtk::Widget>();
-    let start_offset = 30;
 
     let target = adw::CallbackAnimationTarget::new(move |value| {
         let offset = ((1.0 - value) * start_offset as f64) as i32;
@@ -270,18 +575,44 @@ pub fn slide_in_from_bottom(
     animation
 }
 
+/// Fraction of the widget's own extent used as its slide travel distance.
+const SLIDE_TRAVEL_FRACTION: f64 = 0.15;
+/// Bounds (in pixels) the computed travel distance is clamped to, so very
+/// small or very large widgets still get a sensible, visible slide.
+const SLIDE_TRAVEL_MIN_PX: i32 = 24;
+const SLIDE_TRAVEL_MAX_PX: i32 = 160;
+/// Reference monitor width the travel fraction is tuned against.
+const SLIDE_REFERENCE_MONITOR_WIDTH_PX: i32 = 1920;
+
+/// Derive how far (in pixels) `widget` should travel during a slide-in, from
+/// `widget_extent` (its own width or height) and its monitor's geometry, so
+/// the same animation code travels further on a 4K wall display than on a
+/// 1080p kiosk screen. Queried fresh on every call, so it tracks the
+/// widget's current allocation and monitor across resizes and
+/// `notify::scale-factor` changes without needing a cached value.
+fn scaled_slide_distance(widget: &impl IsA<gtk::Widget>, widget_extent: i32) -> i32 {
+    let widget = widget.clone().upcast::<gtk::Widget>();
+
+    let monitor_width = widget
+        .root()
+        .and_then(|root| root.surface())
+        .and_then(|surface| widget.display().monitor_at_surface(&surface))
+        .map(|monitor| monitor.geometry().width())
+        .unwrap_or(SLIDE_REFERENCE_MONITOR_WIDTH_PX);
+
+    let display_scale = monitor_width as f64 / SLIDE_REFERENCE_MONITOR_WIDTH_PX as f64;
+    let travel = widget_extent as f64 * SLIDE_TRAVEL_FRACTION * display_scale;
+
+    (travel as i32).clamp(SLIDE_TRAVEL_MIN_PX, SLIDE_TRAVEL_MAX_PX)
+}
+
 /// Button press animation - quick scale down and back
 pub fn button_press(widget: &impl IsA<gtk::Widget>) -> adw::TimedAnimation {
     let widget_clone = widget.clone().upcast::<gtk::Widget>();
     let _original_opacity = widget_clone.opacity();
 
-    let target = adw::CallbackAnimationTarget::new(move |value| {
-        // Quick opacity dip: 1.0 -> 0.7 -> 1.0
-        let opacity = if value < 0.5 {
-            1.0 - (value * 0.6) // 1.0 -> 0.7
-        } else {
-            0.7 + ((value - 0.5) * 0.6) // 0.7 -> 1.0
-        };
+    // Quick opacity dip: 1.0 -> 0.7 -> 1.0
+    let target = keyframed_target(&[(0.0, 1.0), (0.5, 0.7), (1.0, 1.0)], move |opacity| {
         widget_clone.set_opacity(opacity);
     });
 
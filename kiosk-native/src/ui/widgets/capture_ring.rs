@@ -0,0 +1,115 @@
+//! Looping expanding-ring indicator drawn over the capture button while a
+//! burst of photos is being taken.
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+/// Number of staggered rings drawn per frame.
+const RING_COUNT: u32 = 3;
+/// Radius a ring starts at, as a fraction of the drawing area's half-size.
+const BASE_SCALE: f64 = 0.4;
+/// Radius a ring fades out at, as a fraction of the drawing area's half-size.
+const MAX_SCALE: f64 = 1.0;
+/// How long one ring takes to expand from `BASE_SCALE` to `MAX_SCALE`.
+const LOOP_DURATION_MS: u32 = 1600;
+
+/// Draws one or more concentric rings that continuously expand outward and
+/// fade as they grow, looping forever until [`stop`](Self::stop) is called.
+#[derive(Clone)]
+pub struct CaptureRingIndicator {
+    area: gtk::DrawingArea,
+    phase: Rc<Cell<f64>>,
+    animation: Rc<RefCell<Option<adw::TimedAnimation>>>,
+}
+
+impl CaptureRingIndicator {
+    pub fn new() -> Self {
+        let area = gtk::DrawingArea::new();
+        area.set_can_target(false);
+        area.set_visible(false);
+
+        let phase = Rc::new(Cell::new(0.0));
+        let phase_draw = phase.clone();
+
+        area.set_draw_func(move |_, cr, width, height| {
+            let phase = phase_draw.get();
+            let center_x = width as f64 / 2.0;
+            let center_y = height as f64 / 2.0;
+            let max_radius = width.min(height) as f64 / 2.0;
+
+            for ring in 0..RING_COUNT {
+                // Stagger each ring's phase so they appear evenly spaced.
+                let offset = ring as f64 / RING_COUNT as f64;
+                let ring_phase = (phase + offset).fract();
+
+                let radius = max_radius * (BASE_SCALE + ring_phase * (MAX_SCALE - BASE_SCALE));
+                let alpha = 1.0 - ring_phase;
+
+                cr.set_source_rgba(1.0, 1.0, 1.0, alpha);
+                cr.set_line_width(2.0);
+                cr.arc(center_x, center_y, radius, 0.0, 2.0 * PI);
+                let _ = cr.stroke();
+            }
+        });
+
+        Self {
+            area,
+            phase,
+            animation: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// The drawing area to overlay on the capture button.
+    pub fn widget(&self) -> &gtk::DrawingArea {
+        &self.area
+    }
+
+    /// Start looping the expanding-ring animation. A no-op if already running.
+    pub fn start(&self) {
+        if self.animation.borrow().is_some() {
+            return;
+        }
+        self.area.set_visible(true);
+
+        let phase = self.phase.clone();
+        let area = self.area.clone();
+        let target = adw::CallbackAnimationTarget::new(move |value| {
+            phase.set(value);
+            area.queue_draw();
+        });
+
+        let animation = adw::TimedAnimation::builder()
+            .widget(&self.area)
+            .value_from(0.0)
+            .value_to(1.0)
+            .duration(LOOP_DURATION_MS)
+            .easing(adw::Easing::Linear)
+            .repeat_count(0) // loop forever
+            .target(&target)
+            .build();
+
+        animation.play();
+        *self.animation.borrow_mut() = Some(animation);
+    }
+
+    /// Stop the animation cleanly and clear the rings.
+    pub fn stop(&self) {
+        if let Some(animation) = self.animation.borrow_mut().take() {
+            animation.pause();
+        }
+        self.phase.set(0.0);
+        self.area.set_visible(false);
+        self.area.queue_draw();
+    }
+}
+
+impl Default for CaptureRingIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,60 @@
+//! Toggleable corner overlay showing live pipeline diagnostics (resolution,
+//! codec, bitrate), so operators can verify the camera feed on site.
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+
+use crate::state::StreamInfo;
+
+/// Small label reporting the negotiated stream's resolution, codec and
+/// bitrate. Hidden by default; toggle with [`DiagnosticsOverlay::set_visible`].
+#[derive(Clone)]
+pub struct DiagnosticsOverlay {
+    label: gtk::Label,
+}
+
+impl DiagnosticsOverlay {
+    pub fn new() -> Self {
+        let label = gtk::Label::new(None);
+        label.add_css_class("diagnostics-overlay");
+        label.set_halign(gtk::Align::End);
+        label.set_valign(gtk::Align::Start);
+        label.set_visible(false);
+
+        Self { label }
+    }
+
+    /// The widget to overlay in a corner of the live view
+    pub fn widget(&self) -> &gtk::Label {
+        &self.label
+    }
+
+    /// Update the displayed text from the latest stream diagnostics
+    pub fn set_info(&self, info: Option<&StreamInfo>) {
+        match info {
+            Some(info) => self.label.set_text(&format!(
+                "{}x{} {} {}kbps",
+                info.width,
+                info.height,
+                info.codec,
+                info.bitrate / 1000
+            )),
+            None => self.label.set_text("no stream info"),
+        }
+    }
+
+    /// Toggle the overlay's visibility
+    pub fn set_visible(&self, visible: bool) {
+        self.label.set_visible(visible);
+    }
+
+    pub fn toggle(&self) {
+        self.label.set_visible(!self.label.is_visible());
+    }
+}
+
+impl Default for DiagnosticsOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
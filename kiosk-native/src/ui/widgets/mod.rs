@@ -1,9 +1,21 @@
 //! Reusable UI widgets.
 
+pub mod animations;
 pub mod capture_button;
+pub mod capture_ring;
+pub mod diagnostics_overlay;
+pub mod photo_editor;
 pub mod photo_strip;
+pub mod qr;
 pub mod qr_image;
+pub mod scale_bin;
+pub mod scanner_panel;
 
 pub use capture_button::{create_capture_button, create_capture_status};
+pub use capture_ring::CaptureRingIndicator;
+pub use diagnostics_overlay::DiagnosticsOverlay;
+pub use photo_editor::PhotoEditor;
 pub use photo_strip::{create_photo_strip, update_photo_strip};
-pub use qr_image::{create_wifi_qr_section, create_session_qr_section};
+pub use qr_image::ExpandableQrPanel;
+pub use scale_bin::ScaleBin;
+pub use scanner_panel::ScannerPanel;
@@ -0,0 +1,617 @@
+//! Full-screen photo editor overlay: crop (with optional fixed aspect
+//! ratios), 90° rotation, and brightness/contrast adjustment over a captured
+//! photo, launched from the media viewer or a strip thumbnail's context
+//! action.
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gtk::gdk_pixbuf::{Pixbuf, PixbufRotation};
+
+use crate::app::AppContext;
+use crate::config;
+
+use super::animations;
+
+/// Minimum crop rectangle size, as a fraction of the (rotated) image, so a
+/// drag can't collapse it to nothing.
+const MIN_CROP_FRACTION: f64 = 0.1;
+
+/// Distance, in canvas pixels, a drag's start point may be from a crop
+/// handle and still count as grabbing that handle rather than the body of
+/// the rectangle.
+const HANDLE_GRAB_RADIUS: f64 = 24.0;
+
+/// Print-friendly crop aspect ratios offered alongside freeform cropping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CropAspect {
+    Freeform,
+    FourBySix,
+    Square,
+}
+
+impl CropAspect {
+    /// Width-over-height ratio, or `None` for freeform.
+    fn ratio(self) -> Option<f64> {
+        match self {
+            CropAspect::Freeform => None,
+            CropAspect::FourBySix => Some(4.0 / 6.0),
+            CropAspect::Square => Some(1.0),
+        }
+    }
+}
+
+/// Normalized crop rectangle (`x`, `y`, `w`, `h`, each in `0.0..=1.0`)
+/// relative to the current (rotated) working image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CropRect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl CropRect {
+    const FULL: CropRect = CropRect {
+        x: 0.0,
+        y: 0.0,
+        w: 1.0,
+        h: 1.0,
+    };
+
+    /// Clamp the rectangle to stay within the unit square and above
+    /// `MIN_CROP_FRACTION` in each dimension.
+    fn clamped(self) -> CropRect {
+        let w = self.w.clamp(MIN_CROP_FRACTION, 1.0);
+        let h = self.h.clamp(MIN_CROP_FRACTION, 1.0);
+        let x = self.x.clamp(0.0, 1.0 - w);
+        let y = self.y.clamp(0.0, 1.0 - h);
+        CropRect { x, y, w, h }
+    }
+
+    fn as_canvas(self, canvas_w: f64, canvas_h: f64) -> (f64, f64, f64, f64) {
+        (
+            self.x * canvas_w,
+            self.y * canvas_h,
+            (self.x + self.w) * canvas_w,
+            (self.y + self.h) * canvas_h,
+        )
+    }
+}
+
+/// Mutable editing state for the photo currently loaded in the editor.
+struct EditState {
+    photo_id: String,
+    /// Original decoded image, never mutated - rotation/crop/brightness are
+    /// re-applied to it from scratch so adjustments stay non-destructive
+    /// until confirm.
+    original: Pixbuf,
+    rotation: PixbufRotation,
+    crop: CropRect,
+    aspect: CropAspect,
+    brightness: f64,
+    contrast: f64,
+}
+
+/// Widgets and state backing the full-screen photo editor overlay.
+#[derive(Clone)]
+pub struct PhotoEditor {
+    pub overlay: gtk::Box,
+    /// Shows the rotated + brightness/contrast-adjusted preview (the crop is
+    /// drawn as a guide over it, not applied until confirm).
+    preview: gtk::Picture,
+    /// Transparent layer over `preview` that draws the draggable crop
+    /// rectangle and owns the drag gesture.
+    crop_guide: gtk::DrawingArea,
+    brightness_scale: gtk::Scale,
+    contrast_scale: gtk::Scale,
+    state: Rc<RefCell<Option<EditState>>>,
+    /// Crop handle currently being dragged, if any, as the corner index
+    /// (0=top-left, 1=top-right, 2=bottom-right, 3=bottom-left), or `None`
+    /// to drag the whole rectangle.
+    drag_corner: Rc<Cell<Option<usize>>>,
+    drag_start_crop: Rc<Cell<CropRect>>,
+    on_confirm: Rc<RefCell<Option<Box<dyn Fn(&str, Vec<u8>)>>>>,
+}
+
+impl PhotoEditor {
+    /// Build the (initially hidden) editor overlay.
+    pub fn new() -> Rc<Self> {
+        let overlay = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        overlay.add_css_class("photo-editor");
+        overlay.set_hexpand(true);
+        overlay.set_vexpand(true);
+        overlay.set_visible(false);
+
+        let preview = gtk::Picture::new();
+        preview.set_content_fit(gtk::ContentFit::Contain);
+        preview.set_hexpand(true);
+        preview.set_vexpand(true);
+        preview.add_css_class("photo-editor-preview");
+
+        let crop_guide = gtk::DrawingArea::new();
+        crop_guide.set_hexpand(true);
+        crop_guide.set_vexpand(true);
+
+        let canvas = gtk::Overlay::new();
+        canvas.set_child(Some(&preview));
+        canvas.add_overlay(&crop_guide);
+        overlay.append(&canvas);
+
+        // === Aspect ratio toggles ===
+        let aspect_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        aspect_box.add_css_class("photo-editor-aspects");
+        aspect_box.set_halign(gtk::Align::Center);
+
+        let freeform_button = gtk::ToggleButton::with_label("Freeform");
+        let four_six_button = gtk::ToggleButton::with_label("4×6");
+        four_six_button.set_group(Some(&freeform_button));
+        let square_button = gtk::ToggleButton::with_label("Square");
+        square_button.set_group(Some(&freeform_button));
+        freeform_button.set_active(true);
+
+        aspect_box.append(&freeform_button);
+        aspect_box.append(&four_six_button);
+        aspect_box.append(&square_button);
+        overlay.append(&aspect_box);
+
+        // === Brightness/contrast sliders ===
+        let sliders_box = gtk::Box::new(gtk::Orientation::Vertical, 8);
+        sliders_box.add_css_class("photo-editor-sliders");
+        sliders_box.set_margin_start(24);
+        sliders_box.set_margin_end(24);
+
+        let brightness_scale =
+            gtk::Scale::with_range(gtk::Orientation::Horizontal, -100.0, 100.0, 1.0);
+        brightness_scale.set_value(0.0);
+        brightness_scale.set_hexpand(true);
+        brightness_scale.set_draw_value(false);
+        sliders_box.append(&gtk::Label::new(Some("Brightness")));
+        sliders_box.append(&brightness_scale);
+
+        let contrast_scale =
+            gtk::Scale::with_range(gtk::Orientation::Horizontal, -100.0, 100.0, 1.0);
+        contrast_scale.set_value(0.0);
+        contrast_scale.set_hexpand(true);
+        contrast_scale.set_draw_value(false);
+        sliders_box.append(&gtk::Label::new(Some("Contrast")));
+        sliders_box.append(&contrast_scale);
+
+        overlay.append(&sliders_box);
+
+        // === Toolbar (rotate, cancel, confirm) ===
+        let toolbar = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        toolbar.add_css_class("photo-editor-toolbar");
+        toolbar.set_halign(gtk::Align::Fill);
+        toolbar.set_margin_start(24);
+        toolbar.set_margin_end(24);
+        toolbar.set_margin_top(12);
+        toolbar.set_margin_bottom(24);
+
+        let rotate_button = gtk::Button::from_icon_name("object-rotate-right-symbolic");
+        rotate_button.add_css_class("photo-editor-rotate");
+
+        let cancel_button = gtk::Button::with_label("Cancel");
+        cancel_button.add_css_class("photo-editor-cancel");
+
+        let confirm_button = gtk::Button::with_label("Save");
+        confirm_button.add_css_class("photo-editor-confirm");
+        confirm_button.add_css_class("suggested-action");
+        confirm_button.set_hexpand(true);
+        confirm_button.set_halign(gtk::Align::End);
+
+        toolbar.append(&rotate_button);
+        toolbar.append(&cancel_button);
+        toolbar.append(&confirm_button);
+        overlay.append(&toolbar);
+
+        let editor = Rc::new(Self {
+            overlay,
+            preview,
+            crop_guide,
+            brightness_scale,
+            contrast_scale,
+            state: Rc::new(RefCell::new(None)),
+            drag_corner: Rc::new(Cell::new(None)),
+            drag_start_crop: Rc::new(Cell::new(CropRect::FULL)),
+            on_confirm: Rc::new(RefCell::new(None)),
+        });
+
+        editor.wire_crop_guide();
+        editor.wire_aspect_buttons(&freeform_button, &four_six_button, &square_button);
+        editor.wire_sliders();
+
+        let editor_for_rotate = editor.clone();
+        rotate_button.connect_clicked(move |_| editor_for_rotate.rotate());
+
+        let editor_for_cancel = editor.clone();
+        cancel_button.connect_clicked(move |_| editor_for_cancel.close());
+
+        let editor_for_confirm = editor.clone();
+        confirm_button.connect_clicked(move |_| editor_for_confirm.confirm());
+
+        editor
+    }
+
+    fn wire_crop_guide(self: &Rc<Self>) {
+        let editor = self.clone();
+        self.crop_guide.set_draw_func(move |_, cr, width, height| {
+            editor.draw_crop_guide(cr, width, height);
+        });
+
+        let drag = gtk::GestureDrag::new();
+
+        let editor_begin = self.clone();
+        drag.connect_drag_begin(move |_, x, y| {
+            editor_begin.begin_crop_drag(x, y);
+        });
+
+        let editor_update = self.clone();
+        drag.connect_drag_update(move |_, dx, dy| {
+            editor_update.update_crop_drag(dx, dy);
+        });
+
+        self.crop_guide.add_controller(drag);
+    }
+
+    fn wire_aspect_buttons(
+        self: &Rc<Self>,
+        freeform: &gtk::ToggleButton,
+        four_six: &gtk::ToggleButton,
+        square: &gtk::ToggleButton,
+    ) {
+        let editor = self.clone();
+        freeform.connect_toggled(move |button| {
+            if button.is_active() {
+                editor.set_aspect(CropAspect::Freeform);
+            }
+        });
+
+        let editor = self.clone();
+        four_six.connect_toggled(move |button| {
+            if button.is_active() {
+                editor.set_aspect(CropAspect::FourBySix);
+            }
+        });
+
+        let editor = self.clone();
+        square.connect_toggled(move |button| {
+            if button.is_active() {
+                editor.set_aspect(CropAspect::Square);
+            }
+        });
+    }
+
+    fn wire_sliders(self: &Rc<Self>) {
+        let editor = self.clone();
+        self.brightness_scale.connect_value_changed(move |scale| {
+            if let Some(state) = editor.state.borrow_mut().as_mut() {
+                state.brightness = scale.value();
+            }
+            editor.update_preview();
+        });
+
+        let editor = self.clone();
+        self.contrast_scale.connect_value_changed(move |scale| {
+            if let Some(state) = editor.state.borrow_mut().as_mut() {
+                state.contrast = scale.value();
+            }
+            editor.update_preview();
+        });
+    }
+
+    /// Fetch the full-resolution image for `photo_id`/`web_url` and show the
+    /// editor over it.
+    pub fn open(self: &Rc<Self>, ctx: &Rc<AppContext>, photo_id: &str, web_url: &str) {
+        let full_url = config::photo_url(web_url);
+        let photo_id = photo_id.to_string();
+        let api = ctx.api.clone();
+        let runtime = ctx.runtime.clone();
+        let editor = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result = runtime
+                .spawn(async move { api.fetch_image(&full_url).await })
+                .await;
+
+            match result {
+                Ok(Ok(bytes)) => {
+                    let gbytes = glib::Bytes::from(&bytes);
+                    let stream = gtk::gio::MemoryInputStream::from_bytes(&gbytes);
+                    match Pixbuf::from_stream(&stream, None::<&gtk::gio::Cancellable>) {
+                        Ok(original) => editor.show(photo_id, original),
+                        Err(e) => log::error!("Failed to decode photo for editing: {}", e),
+                    }
+                }
+                Ok(Err(e)) => log::error!("Failed to fetch photo for editing: {}", e),
+                Err(e) => log::error!("Task join error: {}", e),
+            }
+        });
+    }
+
+    fn show(self: &Rc<Self>, photo_id: String, original: Pixbuf) {
+        self.brightness_scale.set_value(0.0);
+        self.contrast_scale.set_value(0.0);
+
+        *self.state.borrow_mut() = Some(EditState {
+            photo_id,
+            original,
+            rotation: PixbufRotation::None,
+            crop: CropRect::FULL,
+            aspect: CropAspect::Freeform,
+            brightness: 0.0,
+            contrast: 0.0,
+        });
+
+        if !self.overlay.is_visible() {
+            self.overlay.set_opacity(0.0);
+            self.overlay.set_visible(true);
+            animations::fade_in(&self.overlay, animations::duration::FAST);
+        }
+        self.update_preview();
+    }
+
+    /// Close the editor without saving.
+    pub fn close(&self) {
+        *self.state.borrow_mut() = None;
+        animations::fade_out(&self.overlay, animations::duration::FAST);
+    }
+
+    fn set_aspect(self: &Rc<Self>, aspect: CropAspect) {
+        let mut state = self.state.borrow_mut();
+        if let Some(state) = state.as_mut() {
+            state.aspect = aspect;
+            if let Some(ratio) = aspect.ratio() {
+                state.crop = fit_aspect(state.crop, ratio).clamped();
+            }
+        }
+        drop(state);
+        self.crop_guide.queue_draw();
+    }
+
+    fn rotate(self: &Rc<Self>) {
+        let mut state = self.state.borrow_mut();
+        if let Some(state) = state.as_mut() {
+            state.rotation = match state.rotation {
+                PixbufRotation::None => PixbufRotation::Clockwise,
+                PixbufRotation::Clockwise => PixbufRotation::Upsidedown,
+                PixbufRotation::Upsidedown => PixbufRotation::Counterclockwise,
+                _ => PixbufRotation::None,
+            };
+            state.crop = CropRect::FULL;
+        }
+        drop(state);
+        self.update_preview();
+    }
+
+    /// Re-render the preview picture from the current rotation and
+    /// brightness/contrast (the crop is a guide only, applied on confirm).
+    fn update_preview(&self) {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else { return };
+
+        let rotated = state
+            .original
+            .rotate_simple(state.rotation)
+            .unwrap_or_else(|| state.original.clone());
+        let adjusted = apply_brightness_contrast(&rotated, state.brightness, state.contrast);
+        self.preview
+            .set_paintable(Some(&gtk::gdk::Texture::for_pixbuf(&adjusted)));
+
+        self.crop_guide.queue_draw();
+    }
+
+    fn begin_crop_drag(&self, x: f64, y: f64) {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else { return };
+        let (canvas_w, canvas_h) = (
+            self.crop_guide.width() as f64,
+            self.crop_guide.height() as f64,
+        );
+        if canvas_w <= 0.0 || canvas_h <= 0.0 {
+            return;
+        }
+
+        let rect = state.crop.as_canvas(canvas_w, canvas_h);
+        self.drag_start_crop.set(state.crop);
+        self.drag_corner.set(corner_at(rect, x, y));
+    }
+
+    fn update_crop_drag(&self, dx: f64, dy: f64) {
+        let mut state = self.state.borrow_mut();
+        let Some(state) = state.as_mut() else { return };
+        let (canvas_w, canvas_h) = (
+            self.crop_guide.width() as f64,
+            self.crop_guide.height() as f64,
+        );
+        if canvas_w <= 0.0 || canvas_h <= 0.0 {
+            return;
+        }
+
+        let dx_frac = dx / canvas_w;
+        let dy_frac = dy / canvas_h;
+        let start = self.drag_start_crop.get();
+        let aspect_ratio = state.aspect.ratio();
+
+        state.crop = match self.drag_corner.get() {
+            Some(corner) => drag_corner(start, corner, dx_frac, dy_frac, aspect_ratio).clamped(),
+            None => CropRect {
+                x: start.x + dx_frac,
+                y: start.y + dy_frac,
+                ..start
+            }
+            .clamped(),
+        };
+
+        drop(state);
+        self.crop_guide.queue_draw();
+    }
+
+    fn draw_crop_guide(&self, cr: &gtk::cairo::Context, width: i32, height: i32) {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else { return };
+
+        let (x0, y0, x1, y1) = state.crop.as_canvas(width as f64, height as f64);
+        cr.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+        cr.set_line_width(2.0);
+        cr.rectangle(x0, y0, x1 - x0, y1 - y0);
+        let _ = cr.stroke();
+    }
+
+    /// Connect the callback invoked with `(photo_id, encoded_jpeg_bytes)`
+    /// when the user confirms their edits.
+    pub fn connect_confirm<F>(&self, callback: F)
+    where
+        F: Fn(&str, Vec<u8>) + 'static,
+    {
+        *self.on_confirm.borrow_mut() = Some(Box::new(callback));
+    }
+
+    fn confirm(&self) {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else { return };
+
+        let edited = cropped_output(state);
+        match edited.save_to_bufferv("jpeg", &["quality"], &["92"]) {
+            Ok(bytes) => {
+                if let Some(callback) = self.on_confirm.borrow().as_ref() {
+                    callback(&state.photo_id, bytes);
+                }
+            }
+            Err(e) => log::error!("Failed to encode edited photo: {}", e),
+        }
+
+        drop(state);
+        self.close();
+    }
+}
+
+impl Default for PhotoEditor {
+    fn default() -> Rc<Self> {
+        Self::new()
+    }
+}
+
+/// Apply the current rotation, brightness/contrast, and crop to `state`'s
+/// original image, producing the final image as it would be saved.
+fn cropped_output(state: &EditState) -> Pixbuf {
+    let rotated = state
+        .original
+        .rotate_simple(state.rotation)
+        .unwrap_or_else(|| state.original.clone());
+    let adjusted = apply_brightness_contrast(&rotated, state.brightness, state.contrast);
+
+    let crop_x = (state.crop.x * adjusted.width() as f64).round() as i32;
+    let crop_y = (state.crop.y * adjusted.height() as f64).round() as i32;
+    let crop_w = ((state.crop.w * adjusted.width() as f64).round() as i32).max(1);
+    let crop_h = ((state.crop.h * adjusted.height() as f64).round() as i32).max(1);
+    adjusted.new_subpixbuf(crop_x, crop_y, crop_w, crop_h)
+}
+
+/// Apply brightness (-100..100, additive) and contrast (-100..100, where 0 is
+/// unchanged) to a copy of `pixbuf`.
+fn apply_brightness_contrast(pixbuf: &Pixbuf, brightness: f64, contrast: f64) -> Pixbuf {
+    if brightness == 0.0 && contrast == 0.0 {
+        return pixbuf.clone();
+    }
+
+    let Some(adjusted) = pixbuf.copy() else {
+        return pixbuf.clone();
+    };
+    let contrast_factor = (259.0 * (contrast + 255.0)) / (255.0 * (259.0 - contrast));
+    let channels = adjusted.n_channels() as usize;
+    let rowstride = adjusted.rowstride() as usize;
+    let (width, height) = (adjusted.width() as usize, adjusted.height() as usize);
+
+    // SAFETY: `adjusted` is a fresh copy we own exclusively, so no other
+    // reference can alias these pixels while we hold `&mut`.
+    let pixels = unsafe { adjusted.pixels() };
+    for row in 0..height {
+        for col in 0..width {
+            let offset = row * rowstride + col * channels;
+            for channel in pixels.iter_mut().skip(offset).take(3) {
+                let value = contrast_factor * (*channel as f64 - 128.0) + 128.0 + brightness;
+                *channel = value.clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    adjusted
+}
+
+/// Adjust `crop` to the given width/height ratio, keeping its center fixed
+/// and shrinking whichever dimension is too large.
+fn fit_aspect(crop: CropRect, ratio: f64) -> CropRect {
+    let center_x = crop.x + crop.w / 2.0;
+    let center_y = crop.y + crop.h / 2.0;
+
+    let (w, h) = if crop.w / crop.h > ratio {
+        (crop.h * ratio, crop.h)
+    } else {
+        (crop.w, crop.w / ratio)
+    };
+
+    CropRect {
+        x: center_x - w / 2.0,
+        y: center_y - h / 2.0,
+        w,
+        h,
+    }
+}
+
+/// Which crop handle, if any, is within grabbing distance of `(x, y)`.
+fn corner_at(rect: (f64, f64, f64, f64), x: f64, y: f64) -> Option<usize> {
+    let (x0, y0, x1, y1) = rect;
+    let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)];
+
+    corners
+        .iter()
+        .position(|(cx, cy)| ((cx - x).powi(2) + (cy - y).powi(2)).sqrt() <= HANDLE_GRAB_RADIUS)
+}
+
+/// Apply a corner drag of `(dx_frac, dy_frac)` to `start`, optionally locking
+/// to `aspect_ratio`.
+fn drag_corner(
+    start: CropRect,
+    corner: usize,
+    dx_frac: f64,
+    dy_frac: f64,
+    aspect_ratio: Option<f64>,
+) -> CropRect {
+    let (x0, y0, x1, y1) = (start.x, start.y, start.x + start.w, start.y + start.h);
+
+    let (mut nx0, mut ny0, mut nx1, mut ny1) = (x0, y0, x1, y1);
+    match corner {
+        0 => {
+            nx0 += dx_frac;
+            ny0 += dy_frac;
+        }
+        1 => {
+            nx1 += dx_frac;
+            ny0 += dy_frac;
+        }
+        2 => {
+            nx1 += dx_frac;
+            ny1 += dy_frac;
+        }
+        _ => {
+            nx0 += dx_frac;
+            ny1 += dy_frac;
+        }
+    }
+
+    let mut crop = CropRect {
+        x: nx0.min(nx1),
+        y: ny0.min(ny1),
+        w: (nx1 - nx0).abs(),
+        h: (ny1 - ny0).abs(),
+    };
+
+    if let Some(ratio) = aspect_ratio {
+        crop = fit_aspect(crop, ratio);
+    }
+
+    crop
+}
@@ -0,0 +1,77 @@
+//! Local QR code rendering.
+//!
+//! Following gnome-control-center's approach, QR codes are rasterized
+//! directly from the encoded module matrix rather than fetched as images
+//! from the backend - no network round-trip, and no re-decode each time a
+//! panel expands.
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+use qrcode::{EcLevel, QrCode};
+
+/// Error-correction level used for locally-rendered QR codes. Medium leaves
+/// enough redundancy to survive glare/smudges on the kiosk's QR panel
+/// without growing the matrix as much as Quartile/High would.
+const EC_LEVEL: EcLevel = EcLevel::M;
+
+/// Modules of solid quiet-zone border added around the encoded matrix, as
+/// required for reliable scanning
+const BORDER_MODULES: u32 = 4;
+
+/// Encode `text` as a QR code and rasterize it into a `gdk::Texture` sized
+/// as close to `target_px` as an integer number of pixels-per-module allows
+/// (rounded down), so modules land on exact pixel boundaries and stay crisp
+/// at any requested size.
+pub fn render_qr(text: &str, target_px: u32) -> gtk::gdk::Texture {
+    let code = match QrCode::with_error_correction_level(text, EC_LEVEL) {
+        Ok(code) => code,
+        Err(e) => {
+            log::error!("Failed to encode QR code: {}", e);
+            return blank_texture();
+        }
+    };
+
+    let matrix_modules = code.width() as u32;
+    let total_modules = matrix_modules + 2 * BORDER_MODULES;
+    let module_px = (target_px / total_modules).max(1);
+    let size_px = module_px * total_modules;
+
+    let colors = code.to_colors();
+    let mut buf = vec![0xffu8; (size_px * size_px * 3) as usize];
+
+    for (i, color) in colors.iter().enumerate() {
+        if *color == qrcode::Color::Light {
+            continue;
+        }
+        let module_row = (i as u32) / matrix_modules;
+        let module_col = (i as u32) % matrix_modules;
+        let x0 = (BORDER_MODULES + module_col) * module_px;
+        let y0 = (BORDER_MODULES + module_row) * module_px;
+
+        for y in y0..y0 + module_px {
+            let row_start = ((y * size_px + x0) * 3) as usize;
+            let row_end = row_start + (module_px * 3) as usize;
+            buf[row_start..row_end].fill(0x00);
+        }
+    }
+
+    texture_from_rgb8(&buf, size_px, size_px)
+}
+
+/// A minimal 1x1 white texture, returned if encoding fails so a broken QR
+/// code degrades to an empty square instead of crashing the kiosk
+fn blank_texture() -> gtk::gdk::Texture {
+    texture_from_rgb8(&[0xff, 0xff, 0xff], 1, 1)
+}
+
+fn texture_from_rgb8(rgb: &[u8], width: u32, height: u32) -> gtk::gdk::Texture {
+    let bytes = glib::Bytes::from(rgb);
+    gtk::gdk::MemoryTexture::new(
+        width as i32,
+        height as i32,
+        gtk::gdk::MemoryFormat::R8g8b8,
+        &bytes,
+        (width * 3) as usize,
+    )
+    .upcast()
+}
@@ -1,4 +1,9 @@
 //! QR code image widget that loads from URL with expand/collapse support.
+//!
+//! QR codes are rasterized at `logical_size * scale_factor()` (see
+//! `render_qr_instant`/`render_qr_crossfade`) so they stay crisp on HiDPI
+//! displays once GTK downsamples the texture to the widget's logical
+//! allocation.
 
 use gtk4 as gtk;
 use gtk4::prelude::*;
@@ -9,13 +14,14 @@ use std::rc::Rc;
 
 use crate::app::AppContext;
 use crate::config;
+use crate::ui::widgets::{animations, qr};
 
 /// Load an image from URL into a Picture widget
 pub fn load_image_into_picture(ctx: &Rc<AppContext>, url: &str, picture: &gtk::Picture) {
     let full_url = if url.starts_with("http") {
         url.to_string()
     } else {
-        format!("{}{}", config::API_BASE, url)
+        format!("{}{}", config::api_base(), url)
     };
 
     let picture = picture.clone();
@@ -49,13 +55,90 @@ pub fn load_image_into_picture(ctx: &Rc<AppContext>, url: &str, picture: &gtk::P
     });
 }
 
+/// A QR `Picture` that can dissolve into a freshly-rasterized texture
+/// instead of popping directly to it, so resizing the panel to a size whose
+/// bitmap was rendered at a different scale doesn't flash. Wraps the base
+/// `Picture` in a `gtk::Overlay` so `crossfade_to` has somewhere to stack the
+/// incoming texture while the outgoing one is still showing.
+struct CrossfadeQr {
+    overlay: gtk::Overlay,
+    picture: gtk::Picture,
+}
+
+impl CrossfadeQr {
+    fn new() -> Self {
+        let picture = gtk::Picture::new();
+        picture.set_hexpand(false);
+        picture.set_vexpand(false);
+        picture.add_css_class("qr-image");
+
+        let overlay = gtk::Overlay::new();
+        overlay.set_child(Some(&picture));
+        overlay.set_hexpand(false);
+        overlay.set_vexpand(false);
+
+        Self { overlay, picture }
+    }
+
+    /// The widget to place in the panel's layout
+    fn widget(&self) -> &gtk::Overlay {
+        &self.overlay
+    }
+
+    fn set_size_request(&self, size: i32) {
+        self.overlay.set_size_request(size, size);
+        self.picture.set_size_request(size, size);
+    }
+
+    /// The base `Picture`'s scale factor, used to size the rasterized
+    /// texture for the monitor it's currently displayed on
+    fn scale_factor(&self) -> i32 {
+        self.picture.scale_factor()
+    }
+
+    /// Set the texture immediately with no transition - used for the first
+    /// render, HiDPI re-renders on a scale-factor change, and a newly set
+    /// session QR
+    fn set_texture(&self, texture: &gtk::gdk::Texture) {
+        self.picture.set_paintable(Some(texture));
+    }
+
+    /// Dissolve from whatever's currently displayed to `texture` over
+    /// `duration_ms`: stack a second `Picture` showing the new texture over
+    /// the old one, fade it in, then swap the base `Picture`'s paintable and
+    /// drop the overlay child so steady state is back to a single `Picture`.
+    fn crossfade_to(&self, texture: gtk::gdk::Texture, duration_ms: u32) {
+        let incoming = gtk::Picture::new();
+        incoming.set_hexpand(false);
+        incoming.set_vexpand(false);
+        incoming.add_css_class("qr-image");
+        incoming.set_paintable(Some(&texture));
+        incoming.set_opacity(0.0);
+        self.overlay.add_overlay(&incoming);
+
+        let base = self.picture.clone();
+        let overlay = self.overlay.clone();
+        let incoming_done = incoming.clone();
+        animations::fade(
+            &incoming,
+            0.0,
+            1.0,
+            duration_ms,
+            Some(Box::new(move || {
+                base.set_paintable(Some(&texture));
+                overlay.remove_overlay(&incoming_done);
+            })),
+        );
+    }
+}
+
 /// Create a QR code item with label below
-fn create_qr_item(qr: &gtk::Picture, label_text: &str) -> gtk::Box {
+fn create_qr_item(qr: &CrossfadeQr, label_text: &str) -> gtk::Box {
     let container = gtk::Box::new(gtk::Orientation::Vertical, 6);
     container.set_halign(gtk::Align::Center);
     container.set_valign(gtk::Align::Center);
 
-    container.append(qr);
+    container.append(qr.widget());
 
     let label = gtk::Label::new(Some(label_text));
     label.add_css_class("qr-label");
@@ -64,24 +147,63 @@ fn create_qr_item(qr: &gtk::Picture, label_text: &str) -> gtk::Box {
     container
 }
 
+/// Rasterize `text` at `logical_size` scaled up by `qr`'s current scale
+/// factor and set it immediately, with no transition - the same HiDPI fix
+/// gnome-control-center applied to its WiFi QR codes.
+fn render_qr_instant(qr: &CrossfadeQr, text: &str, logical_size: u32) {
+    let scale = qr.scale_factor().max(1) as u32;
+    qr.set_texture(&qr::render_qr(text, logical_size * scale));
+}
+
+/// Rasterize `text` at `logical_size` scaled up by `qr`'s current scale
+/// factor and dissolve into it over `duration_ms`, rather than popping
+/// directly to a texture rasterized at a different resolution
+fn render_qr_crossfade(qr: &CrossfadeQr, text: &str, logical_size: u32, duration_ms: u32) {
+    let scale = qr.scale_factor().max(1) as u32;
+    qr.crossfade_to(qr::render_qr(text, logical_size * scale), duration_ms);
+}
+
+/// Drag distance (in pixels) past which the panel counts as fully dragged
+/// toward the collapsed corner, i.e. drag progress reaches 1.0
+const DRAG_DISMISS_DISTANCE_PX: f64 = 140.0;
+
+/// Release velocity (px/s), mirroring `SWIPE_VELOCITY_THRESHOLD` elsewhere in
+/// the UI, past which a flick dismisses the panel even if it hasn't been
+/// dragged past the halfway point
+const DRAG_DISMISS_VELOCITY_THRESHOLD: f64 = 700.0;
+
 /// Expandable QR panel state
 /// Displays QR codes in a horizontal row with labels, expands to fill screen on tap
 pub struct ExpandableQrPanel {
     pub panel: gtk::Box,
-    ctx: Rc<AppContext>,
-    wifi_qr: gtk::Picture,
-    session_qr: gtk::Picture,
+    wifi_qr: CrossfadeQr,
+    session_qr: CrossfadeQr,
     session_box: gtk::Box,
     is_expanded: Rc<Cell<bool>>,
     session_id: Rc<RefCell<Option<String>>>,
-    /// Store animation reference to prevent it from being dropped
-    animation: Rc<RefCell<Option<adw::TimedAnimation>>>,
+    /// Logical (unscaled) size the QR codes are currently rendered at, kept
+    /// around so a `notify::scale-factor` change (e.g. the kiosk window
+    /// moving to a different monitor) can re-rasterize at the same logical
+    /// size but the new physical scale
+    current_size: Rc<Cell<u32>>,
+    /// Most recent drag offset reported by `GestureDrag::connect_drag_update`,
+    /// read back at `drag-end` to compute the release progress
+    drag_offset: Cell<(f64, f64)>,
+    /// Velocity of the release, if `GestureSwipe` recognized one - reset to
+    /// zero at the start of every drag so a stale value from a previous,
+    /// unrelated swipe can't leak into the next release's spring
+    release_velocity: Cell<(f64, f64)>,
+    /// Store animation reference to prevent it from being dropped. Holds the
+    /// base `adw::Animation` type since `animate_size` always drives a
+    /// `SpringAnimation` now, whether triggered by a tap toggle (velocity
+    /// 0.0) or a released drag (velocity from the gesture)
+    animation: Rc<RefCell<Option<adw::Animation>>>,
 }
 
 impl ExpandableQrPanel {
     /// Create an expandable QR panel (small in corner, expands on tap)
     /// Uses horizontal layout with QR codes side by side
-    pub fn new(ctx: &Rc<AppContext>) -> Rc<Self> {
+    pub fn new(_ctx: &Rc<AppContext>) -> Rc<Self> {
         // Main panel - horizontal layout for QR codes in a row
         let panel = gtk::Box::new(gtk::Orientation::Horizontal, 16);
         panel.add_css_class("qr-panel-small");
@@ -94,27 +216,17 @@ impl ExpandableQrPanel {
 
         let is_expanded = Rc::new(Cell::new(false));
         let session_id: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+        let current_size = Rc::new(Cell::new(config::qr_size_small()));
 
         // Create WiFi QR
-        let wifi_qr = gtk::Picture::new();
-        wifi_qr.set_size_request(
-            config::QR_SIZE_SMALL as i32,
-            config::QR_SIZE_SMALL as i32,
-        );
-        wifi_qr.set_hexpand(false);
-        wifi_qr.set_vexpand(false);
-        wifi_qr.add_css_class("qr-image");
-        load_image_into_picture(ctx, &config::wifi_qr_url(config::QR_SIZE_SMALL), &wifi_qr);
+        let wifi_qr = CrossfadeQr::new();
+        let qr_size_small = config::qr_size_small();
+        wifi_qr.set_size_request(qr_size_small as i32);
+        render_qr_instant(&wifi_qr, &config::wifi_credential_string(), qr_size_small);
 
         // Session QR placeholder
-        let session_qr = gtk::Picture::new();
-        session_qr.set_size_request(
-            config::QR_SIZE_SMALL as i32,
-            config::QR_SIZE_SMALL as i32,
-        );
-        session_qr.set_hexpand(false);
-        session_qr.set_vexpand(false);
-        session_qr.add_css_class("qr-image");
+        let session_qr = CrossfadeQr::new();
+        session_qr.set_size_request(qr_size_small as i32);
 
         // WiFi box with label
         let wifi_box = create_qr_item(&wifi_qr, "WIFI");
@@ -128,45 +240,142 @@ impl ExpandableQrPanel {
 
         let qr_panel = Rc::new(Self {
             panel,
-            ctx: ctx.clone(),
             wifi_qr,
             session_qr,
             session_box,
             is_expanded,
             session_id,
+            current_size,
+            drag_offset: Cell::new((0.0, 0.0)),
+            release_velocity: Cell::new((0.0, 0.0)),
             animation: Rc::new(RefCell::new(None)),
         });
 
-        // Click handler
+        // Click handler - only toggles on a plain tap; a tap that turned into
+        // a drag is handled by the drag/swipe gestures below instead
         let gesture = gtk::GestureClick::new();
         let qr_panel_clone = qr_panel.clone();
         gesture.connect_released(move |_, _, _, _| {
-            qr_panel_clone.toggle_expanded();
+            let (dx, dy) = qr_panel_clone.drag_offset.get();
+            if Self::drag_progress(dx, dy) == 0.0 {
+                qr_panel_clone.toggle_expanded();
+            }
         });
         qr_panel.panel.add_controller(gesture);
 
+        // Swipe-to-dismiss, mirroring Fractal's media_viewer drag-to-dismiss:
+        // a GestureSwipe captures the release velocity (if the motion was
+        // fast enough to register as a swipe at all) and a GestureDrag tracks
+        // continuous offset to drive the size interpolation while dragging.
+        // Added before the drag gesture so its velocity is recorded before
+        // `drag-end` reads it back.
+        let swipe_gesture = gtk::GestureSwipe::new();
+        let qr_panel_swipe = qr_panel.clone();
+        swipe_gesture.connect_swipe(move |_, vx, vy| {
+            if qr_panel_swipe.is_expanded.get() {
+                qr_panel_swipe.release_velocity.set((vx, vy));
+            }
+        });
+        qr_panel.panel.add_controller(swipe_gesture);
+
+        let drag_gesture = gtk::GestureDrag::new();
+        let qr_panel_drag_begin = qr_panel.clone();
+        drag_gesture.connect_drag_begin(move |_, _, _| {
+            qr_panel_drag_begin.drag_offset.set((0.0, 0.0));
+            qr_panel_drag_begin.release_velocity.set((0.0, 0.0));
+        });
+        let qr_panel_drag_update = qr_panel.clone();
+        drag_gesture.connect_drag_update(move |_, dx, dy| {
+            if qr_panel_drag_update.is_expanded.get() {
+                qr_panel_drag_update.on_drag_update(dx, dy);
+            }
+        });
+        let qr_panel_drag_end = qr_panel.clone();
+        drag_gesture.connect_drag_end(move |_, dx, dy| {
+            if qr_panel_drag_end.is_expanded.get() {
+                qr_panel_drag_end.on_drag_end(dx, dy);
+            }
+        });
+        qr_panel.panel.add_controller(drag_gesture);
+
+        // Re-rasterize at the current logical size whenever the widget's
+        // scale factor changes (e.g. the kiosk window is dragged to a
+        // monitor with a different HiDPI scale), so both QR codes stay
+        // crisp without waiting for the next expand/collapse
+        let qr_panel_wifi_scale = qr_panel.clone();
+        qr_panel
+            .wifi_qr
+            .picture
+            .connect_notify_local(Some("scale-factor"), move |_, _| {
+                qr_panel_wifi_scale.rerender_wifi();
+            });
+        let qr_panel_session_scale = qr_panel.clone();
         qr_panel
+            .session_qr
+            .picture
+            .connect_notify_local(Some("scale-factor"), move |_, _| {
+                qr_panel_session_scale.rerender_session();
+            });
+
+        qr_panel
+    }
+
+    /// Re-rasterize the WiFi QR at its current logical size and scale factor
+    fn rerender_wifi(&self) {
+        render_qr_instant(
+            &self.wifi_qr,
+            &config::wifi_credential_string(),
+            self.current_size.get(),
+        );
+    }
+
+    /// Re-rasterize the session QR at its current logical size and scale
+    /// factor, if a session is active
+    fn rerender_session(&self) {
+        if let Some(ref id) = *self.session_id.borrow() {
+            render_qr_instant(&self.session_qr, &config::join_url(id), self.current_size.get());
+        }
     }
 
-    /// Toggle expanded state with smooth animation
+    /// Toggle expanded state, springing to the target size with zero initial
+    /// velocity (a tap, unlike a swipe release, carries no momentum)
     fn toggle_expanded(&self) {
         let expanded = !self.is_expanded.get();
+        self.set_expanded(expanded, 0.0);
+    }
+
+    /// Apply the expanded/collapsed state: crossfade the QR codes to the
+    /// target size's texture, flip the CSS class and corner/center
+    /// positioning, and spring the currently-displayed size to it from
+    /// wherever it is now (mid-drag sizes included, so a released swipe
+    /// continues smoothly).
+    fn set_expanded(&self, expanded: bool, initial_velocity: f64) {
         self.is_expanded.set(expanded);
 
-        let (from_size, to_size) = if expanded {
-            (config::QR_SIZE_SMALL, config::QR_SIZE_LARGE)
+        let from_size = self.current_size.get();
+        let to_size = if expanded {
+            config::qr_size_large()
         } else {
-            (config::QR_SIZE_LARGE, config::QR_SIZE_SMALL)
+            config::qr_size_small()
         };
 
-        // Reload QR codes at the target size for crisp rendering
-        load_image_into_picture(&self.ctx, &config::wifi_qr_url(to_size), &self.wifi_qr);
+        // Dissolve into the target-size texture rather than popping to it,
+        // since it's rasterized at a different resolution than what's
+        // currently on screen
+        self.current_size.set(to_size);
+        render_qr_crossfade(
+            &self.wifi_qr,
+            &config::wifi_credential_string(),
+            to_size,
+            animations::duration::NORMAL,
+        );
 
         if let Some(ref id) = *self.session_id.borrow() {
-            load_image_into_picture(
-                &self.ctx,
-                &config::session_qr_url(id, to_size),
+            render_qr_crossfade(
                 &self.session_qr,
+                &config::join_url(id),
+                to_size,
+                animations::duration::NORMAL,
             );
         }
 
@@ -188,60 +397,123 @@ impl ExpandableQrPanel {
         }
 
         // Animate the size transition
-        self.animate_size(from_size, to_size);
+        self.animate_size(from_size, to_size, initial_velocity);
     }
 
-    /// Animate QR code size with smooth easing
-    fn animate_size(&self, from_size: u32, to_size: u32) {
+    /// Spring parameters tuned so a released drag settles quickly without
+    /// the bouncy overshoot a lower damping ratio would add - the panel
+    /// should feel like it's being caught, not like it's on a rubber band.
+    fn spring_params() -> adw::SpringParams {
+        adw::SpringParams::new(1.0, 1.0, 500.0)
+    }
+
+    /// Spring the QR code size from `from_size` to `to_size`, starting with
+    /// `initial_velocity` (size units/sec) so a fast swipe release carries
+    /// its momentum into the animation instead of starting from rest
+    fn animate_size(&self, from_size: u32, to_size: u32, initial_velocity: f64) {
         // Cancel any existing animation
         if let Some(ref anim) = *self.animation.borrow() {
             anim.skip();
         }
 
-        let wifi_qr = self.wifi_qr.clone();
-        let session_qr = self.session_qr.clone();
+        let wifi_overlay = self.wifi_qr.overlay.clone();
+        let wifi_picture = self.wifi_qr.picture.clone();
+        let session_overlay = self.session_qr.overlay.clone();
+        let session_picture = self.session_qr.picture.clone();
         let session_box = self.session_box.clone();
 
         // Create callback target that updates widget sizes
         let target = adw::CallbackAnimationTarget::new(move |value| {
             let size = value as i32;
-            wifi_qr.set_size_request(size, size);
+            wifi_overlay.set_size_request(size, size);
+            wifi_picture.set_size_request(size, size);
             if session_box.is_visible() {
-                session_qr.set_size_request(size, size);
+                session_overlay.set_size_request(size, size);
+                session_picture.set_size_request(size, size);
             }
         });
 
-        // Create timed animation (300ms with ease-out-cubic)
-        let animation = adw::TimedAnimation::builder()
+        let animation = adw::SpringAnimation::builder()
             .widget(&self.panel)
             .value_from(from_size as f64)
             .value_to(to_size as f64)
-            .duration(300)
-            .easing(adw::Easing::EaseOutCubic)
+            .spring_params(&Self::spring_params())
+            .initial_velocity(initial_velocity)
             .target(&target)
             .build();
 
         animation.play();
-        *self.animation.borrow_mut() = Some(animation);
+        *self.animation.borrow_mut() = Some(animation.upcast());
+    }
+
+    /// Drag progress in `[0.0, 1.0]`: 0 at the drag's start, 1 once it's
+    /// travelled `DRAG_DISMISS_DISTANCE_PX` downward or outward. Upward
+    /// motion doesn't count toward dismissal, only away from it.
+    fn drag_progress(dx: f64, dy: f64) -> f64 {
+        let distance = dx.hypot(dy.max(0.0));
+        (distance / DRAG_DISMISS_DISTANCE_PX).clamp(0.0, 1.0)
+    }
+
+    /// Drive the same size interpolation `animate_size` uses, but from live
+    /// drag offset rather than a timer, so the panel visibly shrinks as the
+    /// user's finger pulls it toward the corner. No texture crossfade here -
+    /// it only regenerates once the gesture settles on a final size.
+    fn on_drag_update(&self, dx: f64, dy: f64) {
+        self.drag_offset.set((dx, dy));
+
+        let progress = Self::drag_progress(dx, dy);
+        let large = config::qr_size_large() as f64;
+        let small = config::qr_size_small() as f64;
+        let size = (large - (large - small) * progress).round() as i32;
+
+        self.wifi_qr.set_size_request(size);
+        if self.session_box.is_visible() {
+            self.session_qr.set_size_request(size);
+        }
+        self.current_size.set(size as u32);
+    }
+
+    /// Decide whether a released drag dismisses the panel (past the halfway
+    /// point, or fast enough to register as a swipe) or springs back to
+    /// fully expanded, carrying over whatever release velocity was captured
+    fn on_drag_end(&self, dx: f64, dy: f64) {
+        let progress = Self::drag_progress(dx, dy);
+        let (vx, vy) = self.release_velocity.get();
+        let velocity = vx.hypot(vy.max(0.0));
+
+        self.drag_offset.set((0.0, 0.0));
+        self.release_velocity.set((0.0, 0.0));
+
+        let dismiss = progress > 0.5 || velocity > DRAG_DISMISS_VELOCITY_THRESHOLD;
+
+        // Project the release speed onto the size axis so the spring's
+        // initial velocity matches the direction the drag was already
+        // animating the size in (shrinking toward collapse)
+        let large = config::qr_size_large() as f64;
+        let small = config::qr_size_small() as f64;
+        let size_velocity = -velocity * (large - small) / DRAG_DISMISS_DISTANCE_PX;
+
+        self.set_expanded(!dismiss, size_velocity);
     }
 
     /// Update the session QR with fade-in animation
-    pub fn set_session(&self, ctx: &Rc<AppContext>, session_id: &str) {
+    pub fn set_session(&self, _ctx: &Rc<AppContext>, session_id: &str) {
         *self.session_id.borrow_mut() = Some(session_id.to_string());
 
         let size = if self.is_expanded.get() {
-            config::QR_SIZE_LARGE
+            config::qr_size_large()
         } else {
-            config::QR_SIZE_SMALL
+            config::qr_size_small()
         };
 
-        self.session_qr.set_size_request(size as i32, size as i32);
-        load_image_into_picture(ctx, &config::session_qr_url(session_id, size), &self.session_qr);
+        self.current_size.set(size);
+        self.session_qr.set_size_request(size as i32);
+        render_qr_instant(&self.session_qr, &config::join_url(session_id), size);
 
         // Fade in the session box
         self.session_box.set_opacity(0.0);
         self.session_box.set_visible(true);
-        super::animations::fade_in(&self.session_box, super::animations::duration::NORMAL);
+        animations::fade_in(&self.session_box, animations::duration::NORMAL);
     }
 
     /// Hide the session QR with fade-out animation
@@ -249,13 +521,13 @@ impl ExpandableQrPanel {
         *self.session_id.borrow_mut() = None;
 
         if self.session_box.is_visible() {
-            let session_qr = self.session_qr.clone();
+            let session_qr = self.session_qr.picture.clone();
             let session_box = self.session_box.clone();
-            super::animations::fade(
+            animations::fade(
                 &self.session_box,
                 1.0,
                 0.0,
-                super::animations::duration::FAST,
+                animations::duration::FAST,
                 Some(Box::new(move || {
                     session_qr.set_paintable(None::<&gtk::gdk::Paintable>);
                     session_box.set_visible(false);
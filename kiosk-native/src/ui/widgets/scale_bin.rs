@@ -0,0 +1,117 @@
+//! A thin bin widget that scales (and optionally translates) its child at
+//! paint time via a `gsk::Transform`.
+//!
+//! Unlike mutating `set_size_request` every frame, transforming in `snapshot`
+//! never triggers a relayout of the parent container, so `animations::scale_bounce`
+//! can "pop" a widget without shifting its neighbors, and a lightbox reveal can
+//! slide+scale in from a thumbnail's position without the grid around it
+//! reflowing.
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use gtk4::{glib, graphene, gsk};
+
+mod imp {
+    use super::*;
+    use std::cell::Cell;
+
+    pub struct ScaleBin {
+        pub scale: Cell<f64>,
+        pub translate_x: Cell<f64>,
+        pub translate_y: Cell<f64>,
+    }
+
+    impl Default for ScaleBin {
+        fn default() -> Self {
+            Self {
+                scale: Cell::new(1.0),
+                translate_x: Cell::new(0.0),
+                translate_y: Cell::new(0.0),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ScaleBin {
+        const NAME: &'static str = "PicpopScaleBin";
+        type Type = super::ScaleBin;
+        type ParentType = gtk::Widget;
+    }
+
+    impl ObjectImpl for ScaleBin {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().set_layout_manager(Some(gtk::BinLayout::new()));
+        }
+    }
+
+    impl WidgetImpl for ScaleBin {
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let widget = self.obj();
+            let scale = self.scale.get() as f32;
+            let translate =
+                graphene::Point::new(self.translate_x.get() as f32, self.translate_y.get() as f32);
+            let center =
+                graphene::Point::new(widget.width() as f32 / 2.0, widget.height() as f32 / 2.0);
+
+            let transform = gsk::Transform::new()
+                .translate(&translate)
+                .translate(&center)
+                .scale(scale, scale)
+                .translate(&graphene::Point::new(-center.x(), -center.y()));
+
+            snapshot.save();
+            snapshot.transform(&transform);
+            self.parent_snapshot(snapshot);
+            snapshot.restore();
+        }
+    }
+}
+
+glib::wrapper! {
+    /// A single-child bin whose `scale` property is applied as a GSK
+    /// transform at paint time rather than by resizing the widget.
+    pub struct ScaleBin(ObjectSubclass<imp::ScaleBin>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl ScaleBin {
+    /// Wrap `child` in a new `ScaleBin` at scale 1.0.
+    pub fn new(child: &impl IsA<gtk::Widget>) -> Self {
+        let bin: Self = glib::Object::new();
+        child.set_parent(&bin);
+        bin
+    }
+
+    /// Set the paint-time scale factor and queue a redraw (no relayout).
+    pub fn set_scale(&self, scale: f64) {
+        self.imp().scale.set(scale);
+        self.queue_draw();
+    }
+
+    /// Current paint-time scale factor.
+    pub fn scale(&self) -> f64 {
+        self.imp().scale.get()
+    }
+
+    /// Set the paint-time translation (in pixels, applied before the scale)
+    /// and queue a redraw (no relayout).
+    pub fn set_translate(&self, x: f64, y: f64) {
+        self.imp().translate_x.set(x);
+        self.imp().translate_y.set(y);
+        self.queue_draw();
+    }
+
+    /// Current paint-time translation, in pixels.
+    pub fn translate(&self) -> (f64, f64) {
+        (self.imp().translate_x.get(), self.imp().translate_y.get())
+    }
+}
+
+impl Default for ScaleBin {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
@@ -0,0 +1,114 @@
+//! Camera-based QR scanner panel, for kiosk pairing/setup without typing -
+//! scan a session's join URL to claim it on this kiosk, or a
+//! `PICPOP-CFG:wifi_ssid=...;wifi_password=...;server=...` payload to
+//! reconfigure the backend/WiFi it advertises.
+//!
+//! Scanning holds the camera open, which the main live preview (see
+//! `video::pipeline::VideoPipeline`) otherwise has exclusive use of, so the
+//! pipeline here is only ever running while the panel itself is mapped -
+//! started on `connect_map`, torn down on `connect_unmap`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use gtk4 as gtk;
+use gtk4::prelude::*;
+
+use crate::app::{AppContext, AppMessage};
+use crate::video::ScannerPipeline;
+
+/// Live camera preview with QR decoding, gated on widget visibility so the
+/// camera is only held open while a setup/pairing flow actually needs it.
+pub struct ScannerPanel {
+    container: gtk::Box,
+    picture: gtk::Picture,
+    pipeline: RefCell<Option<ScannerPipeline>>,
+    /// Last payload forwarded to `AppContext`, so a code held steadily in
+    /// frame (decoded on every sample, dozens of times a second) is only
+    /// acted on once instead of repeatedly reconnecting/rewriting config.
+    /// Shared with the pipeline's decode callback, which fires on a
+    /// GStreamer thread, hence `Arc<Mutex<_>>` rather than a plain `RefCell`.
+    last_scanned: Arc<Mutex<Option<String>>>,
+    ctx: Rc<AppContext>,
+}
+
+impl ScannerPanel {
+    pub fn new(ctx: &Rc<AppContext>) -> Rc<Self> {
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        container.add_css_class("scanner-panel");
+
+        let picture = gtk::Picture::new();
+        picture.set_content_fit(gtk::ContentFit::Cover);
+        picture.set_hexpand(true);
+        picture.set_vexpand(true);
+        container.append(&picture);
+
+        let panel = Rc::new(Self {
+            container,
+            picture,
+            pipeline: RefCell::new(None),
+            last_scanned: Arc::new(Mutex::new(None)),
+            ctx: ctx.clone(),
+        });
+
+        let start_panel = panel.clone();
+        panel.container.connect_map(move |_| start_panel.start());
+
+        let stop_panel = panel.clone();
+        panel.container.connect_unmap(move |_| stop_panel.stop());
+
+        panel
+    }
+
+    /// The widget to place wherever a scan action is offered
+    pub fn widget(&self) -> &gtk::Box {
+        &self.container
+    }
+
+    /// Start the camera and begin decoding, if not already running
+    fn start(self: &Rc<Self>) {
+        if self.pipeline.borrow().is_some() {
+            return;
+        }
+
+        let pipeline = match ScannerPipeline::new() {
+            Ok(pipeline) => pipeline,
+            Err(e) => {
+                log::error!("Failed to start QR scanner pipeline: {}", e);
+                return;
+            }
+        };
+
+        self.picture.set_paintable(Some(pipeline.paintable()));
+        *self.last_scanned.lock().unwrap() = None;
+
+        let tx = self.ctx.message_tx.clone();
+        let last_scanned = self.last_scanned.clone();
+        pipeline.on_decode(move |text| {
+            let mut last_scanned = last_scanned.lock().unwrap();
+            if last_scanned.as_deref() == Some(text.as_str()) {
+                return;
+            }
+            *last_scanned = Some(text.clone());
+            tx.send(AppMessage::QrScanned(text));
+        });
+
+        if let Err(e) = pipeline.play() {
+            log::error!("Failed to start QR scanner pipeline: {}", e);
+            return;
+        }
+
+        *self.pipeline.borrow_mut() = Some(pipeline);
+    }
+
+    /// Stop and drop the pipeline, releasing the camera
+    fn stop(&self) {
+        if let Some(pipeline) = self.pipeline.borrow_mut().take() {
+            if let Err(e) = pipeline.stop() {
+                log::error!("Failed to stop QR scanner pipeline: {}", e);
+            }
+        }
+        self.picture.set_paintable(None::<&gtk::gdk::Paintable>);
+    }
+}
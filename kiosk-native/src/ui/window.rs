@@ -5,8 +5,9 @@ use gtk4::prelude::*;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::app::{AppContext, AppMessage, KioskState, SessionData};
-use crate::state::{KioskCommand, KioskEvent};
+use crate::api::PhotoInfo;
+use crate::app::{AppContext, AppMessage};
+use crate::state::{KioskCommand, KioskEvent, KioskState, SessionData};
 use crate::ui::{countdown, lightbox, session, welcome};
 
 /// Main window containing all screens
@@ -16,6 +17,11 @@ pub struct MainWindow {
     ctx: Rc<AppContext>,
     video_paintable: gtk::gdk::Paintable,
     session_widgets: RefCell<Option<session::SessionWidgets>>,
+    attract_slideshow: RefCell<Option<Rc<welcome::AttractSlideshow>>>,
+    /// Fetch-staleness guard for the currently-open lightbox, created by
+    /// `show_lightbox` and reused by every subsequent `update_lightbox` call
+    /// so in-flight fetches can tell they've been navigated past
+    lightbox_guard: RefCell<Option<lightbox::LightboxGuard>>,
 }
 
 impl MainWindow {
@@ -63,6 +69,8 @@ impl MainWindow {
             ctx,
             video_paintable,
             session_widgets: RefCell::new(None),
+            attract_slideshow: RefCell::new(None),
+            lightbox_guard: RefCell::new(None),
         });
 
         // Set up initial welcome screen
@@ -101,6 +109,22 @@ impl MainWindow {
                 // Image loading is handled by individual widgets
                 log::debug!("Image loaded: {} ({} bytes)", url, bytes.len());
             }
+            AppMessage::SendSignal(msg) => {
+                self.ctx.forward_signal(msg);
+            }
+            AppMessage::RemoteSdpAnswer(sdp) => {
+                self.ctx.apply_remote_sdp_answer(&sdp);
+            }
+            AppMessage::RemoteIceCandidate {
+                candidate,
+                sdp_mline_index,
+            } => {
+                self.ctx
+                    .apply_remote_ice_candidate(&candidate, sdp_mline_index);
+            }
+            AppMessage::QrScanned(text) => {
+                self.ctx.handle_scanned_qr(&text);
+            }
         }
     }
 
@@ -112,6 +136,8 @@ impl MainWindow {
         let countdown_value = sm.countdown_value;
         let lightbox_index = sm.lightbox_index;
         let is_loading = sm.is_loading;
+        let attract_mode_active = sm.attract_mode_active;
+        let recent_photos = sm.recent_photos.clone();
         let error = sm.error.clone();
         drop(sm);
 
@@ -122,6 +148,10 @@ impl MainWindow {
                 }
                 // Update button state
                 self.update_welcome_button(is_loading, error.as_deref());
+                self.update_welcome_attract_mode(attract_mode_active, recent_photos);
+                if let Some(ref widgets) = *self.session_widgets.borrow() {
+                    widgets.set_previews_active(false);
+                }
             }
 
             KioskState::Session => {
@@ -137,6 +167,9 @@ impl MainWindow {
                 if self.stack.child_by_name("countdown").is_some() {
                     self.hide_countdown();
                 }
+                if let Some(ref widgets) = *self.session_widgets.borrow() {
+                    widgets.set_previews_active(true);
+                }
             }
 
             KioskState::Countdown => {
@@ -147,6 +180,9 @@ impl MainWindow {
                         self.show_countdown(value);
                     }
                 }
+                if let Some(ref widgets) = *self.session_widgets.borrow() {
+                    widgets.set_previews_active(false);
+                }
             }
 
             KioskState::Capturing => {
@@ -155,6 +191,12 @@ impl MainWindow {
                 }
             }
 
+            KioskState::Processing => {
+                if let Some(ref widgets) = *self.session_widgets.borrow() {
+                    widgets.show_processing();
+                }
+            }
+
             KioskState::Lightbox => {
                 if let Some(index) = lightbox_index {
                     if self.stack.visible_child_name().as_deref() == Some("lightbox") {
@@ -175,7 +217,7 @@ impl MainWindow {
         }
 
         let ctx = self.ctx.clone();
-        let screen =
+        let (screen, slideshow) =
             welcome::create_welcome_screen(&self.ctx, &self.video_paintable, false, move || {
                 ctx.send_event(KioskEvent::StartSession)
             });
@@ -183,6 +225,7 @@ impl MainWindow {
         self.stack.set_visible_child_name("welcome");
 
         *self.session_widgets.borrow_mut() = None;
+        *self.attract_slideshow.borrow_mut() = Some(slideshow);
     }
 
     /// Update welcome screen button
@@ -194,6 +237,18 @@ impl MainWindow {
         }
     }
 
+    /// Toggle the welcome screen's attract-mode visual treatment and photo
+    /// slideshow
+    fn update_welcome_attract_mode(&self, active: bool, recent_photos: Vec<PhotoInfo>) {
+        if let Some(child) = self.stack.child_by_name("welcome") {
+            if let Some(screen) = child.downcast_ref::<gtk::Overlay>() {
+                if let Some(ref slideshow) = *self.attract_slideshow.borrow() {
+                    welcome::set_attract_mode(screen, slideshow, &self.ctx, active, recent_photos);
+                }
+            }
+        }
+    }
+
     /// Show the session screen
     fn show_session(self: &Rc<Self>, session_id: &str) {
         let session = self.ctx.session().unwrap_or_default();
@@ -222,15 +277,63 @@ impl MainWindow {
         self.stack.add_named(&screen, Some("session"));
         self.stack.set_visible_child_name("session");
 
+        let win = self.clone();
+        widgets.connect_photo_action(move |photo_id, action| {
+            win.handle_photo_action(photo_id, action);
+        });
+
+        let ctx4 = self.ctx.clone();
+        widgets.connect_photo_edited(move |photo_id, bytes| {
+            ctx4.send_event(KioskEvent::UploadEditedPhoto {
+                photo_id: photo_id.to_string(),
+                bytes,
+            });
+        });
+
         *self.session_widgets.borrow_mut() = Some(widgets);
     }
 
+    /// Handle a strip thumbnail's context-menu action. Pin/Unpin is already
+    /// applied client-side by the context menu itself (badge + sort order);
+    /// Edit opens the full-screen editor on the looked-up photo, while
+    /// Delete/MarkForPrint are forwarded to the state machine to call the
+    /// backend.
+    fn handle_photo_action(self: &Rc<Self>, photo_id: &str, action: session::PhotoAction) {
+        match action {
+            session::PhotoAction::Edit => {
+                let photo = self.ctx.state_machine.borrow().session.as_ref().and_then(
+                    |s| s.photos.iter().find(|p| p.id == photo_id).cloned(),
+                );
+                if let Some(photo) = photo {
+                    if let Some(ref widgets) = *self.session_widgets.borrow() {
+                        widgets.open_photo_editor(&self.ctx, &photo);
+                    }
+                }
+            }
+            session::PhotoAction::Delete => {
+                self.ctx.send_event(KioskEvent::DeletePhoto {
+                    photo_id: photo_id.to_string(),
+                });
+            }
+            session::PhotoAction::MarkForPrint => {
+                self.ctx.send_event(KioskEvent::MarkPhotoForPrint {
+                    photo_id: photo_id.to_string(),
+                });
+            }
+            session::PhotoAction::Pin | session::PhotoAction::Unpin => {
+                // Applied directly by the context menu's own SimpleAction
+                // handlers; nothing further to do here.
+            }
+        }
+    }
+
     /// Update session screen widgets
     fn update_session_widgets(self: &Rc<Self>, session: &Option<SessionData>) {
         if let Some(ref widgets) = *self.session_widgets.borrow() {
             if let Some(ref sess) = session {
                 widgets.set_phone_count(sess.phone_count);
                 widgets.set_capturing(false);
+                widgets.set_stream_info(self.ctx.state_machine.borrow().stream_info.as_ref());
 
                 let ctx = self.ctx.clone();
                 widgets.update_photos(&self.ctx, &sess.photos, move |idx| {
@@ -282,10 +385,11 @@ impl MainWindow {
         let ctx1 = self.ctx.clone();
         let ctx2 = self.ctx.clone();
 
-        let lb = lightbox::create_lightbox(
+        let (lb, guard) = lightbox::create_lightbox(
             &self.ctx,
             &photos,
             index,
+            None,
             move || ctx1.send_event(KioskEvent::CloseLightbox),
             move |new_idx| ctx2.send_event(KioskEvent::NavigateLightbox(new_idx)),
         );
@@ -297,6 +401,7 @@ impl MainWindow {
 
         self.stack.add_named(&lb, Some("lightbox"));
         self.stack.set_visible_child_name("lightbox");
+        *self.lightbox_guard.borrow_mut() = Some(guard);
     }
 
     /// Update lightbox to show a different photo
@@ -312,7 +417,9 @@ impl MainWindow {
 
         if let Some(child) = self.stack.child_by_name("lightbox") {
             if let Some(lb) = child.downcast_ref::<gtk::Box>() {
-                lightbox::update_lightbox(&self.ctx, lb, &photos, index);
+                if let Some(ref guard) = *self.lightbox_guard.borrow() {
+                    lightbox::update_lightbox(&self.ctx, lb, &photos, index, guard);
+                }
             }
         }
     }
@@ -0,0 +1,7 @@
+//! GStreamer pipelines: the live camera preview and the QR code scanner.
+
+pub mod pipeline;
+pub mod scanner;
+
+pub use pipeline::VideoPipeline;
+pub use scanner::ScannerPipeline;
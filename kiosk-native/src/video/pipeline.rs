@@ -1,25 +1,145 @@
-//! GStreamer pipeline for MJPEG camera preview with auto-reconnect.
+//! GStreamer pipeline for camera preview (MJPEG or WebRTC) with auto-reconnect.
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use gstreamer as gst;
 use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_sdp as gst_sdp;
+use gstreamer_webrtc as gst_webrtc;
 use gtk4 as gtk;
 use thiserror::Error;
 
 use crate::config;
 
-/// Delay before attempting to reconnect after an error (in milliseconds)
+/// Default base delay before attempting to reconnect after an error (in milliseconds)
 const RECONNECT_DELAY_MS: u64 = 2000;
 
-/// How often to check for stale frames (in milliseconds)  
+/// Default how often to check for stale frames (in milliseconds)
 const STALE_CHECK_INTERVAL_MS: u64 = 3000;
 
-/// If no new frame for this long, consider stream stale (in milliseconds)
+/// Default if no new frame for this long, consider stream stale (in milliseconds)
 const STALE_THRESHOLD_MS: u64 = 5000;
 
+/// Default maximum number of restart attempts before giving up
+const MAX_RESTART_ATTEMPTS: u32 = 10;
+
+/// Default ceiling for the exponential reconnect backoff (in milliseconds)
+const MAX_RECONNECT_DELAY_MS: u64 = 30_000;
+
+/// Test pattern shown on the fallback input while the live feed is down
+const FALLBACK_PATTERN: &str = "snow";
+
+/// Preferred resolution for the live preview, mirroring the gst-wpe demo's
+/// `VideoResolution` knob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoResolution {
+    P480,
+    P720,
+    P1080,
+}
+
+impl VideoResolution {
+    /// Target frame height in pixels, used to build the scaling capsfilter
+    fn height(self) -> i32 {
+        match self {
+            VideoResolution::P480 => 480,
+            VideoResolution::P720 => 720,
+            VideoResolution::P1080 => 1080,
+        }
+    }
+}
+
+/// Reconnect/stale-detection policy, mirroring `fallbacksrc`'s
+/// `restart-timeout` / `retry-timeout` properties
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Base delay before the first reconnect attempt (in milliseconds)
+    pub base_delay_ms: u64,
+    /// Ceiling the exponential backoff delay is capped at (in milliseconds)
+    pub max_delay_ms: u64,
+    /// Number of restart attempts before giving up
+    pub max_attempts: u32,
+    /// How often to check for stale frames (in milliseconds)
+    pub stale_check_interval_ms: u64,
+    /// If no new frame for this long, consider the stream stale (in milliseconds)
+    pub stale_threshold_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: RECONNECT_DELAY_MS,
+            max_delay_ms: MAX_RECONNECT_DELAY_MS,
+            max_attempts: MAX_RESTART_ATTEMPTS,
+            stale_check_interval_ms: STALE_CHECK_INTERVAL_MS,
+            stale_threshold_ms: STALE_THRESHOLD_MS,
+        }
+    }
+}
+
+/// Settings accepted by `VideoPipeline::with_config()`
+#[derive(Debug, Clone)]
+pub struct VideoPipelineConfig {
+    /// MJPEG source URL (ignored for `PreviewTransport::WebRtc`)
+    pub url: String,
+    /// Preferred preview resolution
+    pub resolution: VideoResolution,
+    /// `max-size-buffers` for the pipeline's internal queues
+    pub queue_max_buffers: u32,
+    /// Reconnect/stale-detection policy
+    pub retry: RetryPolicy,
+}
+
+impl Default for VideoPipelineConfig {
+    fn default() -> Self {
+        Self {
+            url: config::camera_preview_url(),
+            resolution: VideoResolution::P720,
+            queue_max_buffers: 3,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Why the pipeline most recently scheduled a reconnect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryReason {
+    /// No retry has happened yet
+    #[default]
+    None,
+    /// A GStreamer bus error
+    Error,
+    /// Unexpected end-of-stream
+    Eos,
+    /// The stale-frame detector didn't see a buffer in time
+    Stale,
+}
+
+/// Point-in-time snapshot of pipeline health, mirroring the `num_retry` /
+/// `last_retry_reason` / `buffering_percent` properties exposed by GStreamer's
+/// `fallbacksrc` element.
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineStats {
+    pub num_retry: u64,
+    pub last_retry_reason: RetryReason,
+    pub buffering_percent: i32,
+    pub total_frames: u64,
+    pub ms_since_last_frame: u64,
+}
+
+/// Negotiated stream diagnostics read off the pipeline's caps and tags, for
+/// a debug overlay operators can use to verify the camera feed on site.
+#[derive(Debug, Clone, Default)]
+pub struct StreamInfo {
+    pub width: i32,
+    pub height: i32,
+    pub codec: String,
+    pub bitrate: u32,
+}
+
 #[derive(Error, Debug)]
 pub enum PipelineError {
     #[error("GStreamer error: {0}")]
@@ -30,6 +150,22 @@ pub enum PipelineError {
     ElementCreation(String),
     #[error("State change failed")]
     StateChange,
+    #[error("No snapshot frame available yet")]
+    NoSnapshot,
+}
+
+/// Outgoing WebRTC signalling payload produced by `webrtcbin`, to be forwarded to
+/// the remote peer (typically piggybacked on the kiosk `websocket::WsHandle`).
+/// Only ever emitted when the pipeline is built with `config::PreviewTransport::WebRtc`.
+#[derive(Debug, Clone)]
+pub enum SignalMessage {
+    /// Local SDP offer, generated once `webrtcbin` negotiates
+    Offer(String),
+    /// Local ICE candidate discovered during gathering
+    IceCandidate {
+        candidate: String,
+        sdp_mline_index: u32,
+    },
 }
 
 /// Video pipeline for camera preview
@@ -41,19 +177,60 @@ pub struct VideoPipeline {
     last_frame_time: Arc<AtomicU64>,
     /// Total frames received
     frame_count: Arc<AtomicU64>,
+    /// input-selector switching between the live feed and the fallback test pattern
+    input_selector: gst::Element,
+    /// Sink pad fed by the live (camera) branch
+    live_pad: gst::Pad,
+    /// Sink pad fed by the `videotestsrc` placeholder branch
+    fallback_pad: gst::Pad,
+    /// Whether the input-selector is currently showing the fallback placeholder
+    showing_fallback: Arc<AtomicBool>,
+    /// Number of reconnect attempts made so far
+    num_retry: Arc<AtomicU64>,
+    /// Reason for the most recent retry
+    last_retry_reason: Arc<Mutex<RetryReason>>,
+    /// Most recently reported buffering percentage (0-100)
+    buffering_percent: Arc<AtomicI32>,
+    /// Sink that holds the most recent JPEG-encoded frame for `capture_still()`
+    snapshot_sink: gst_app::AppSink,
+    /// Latest resolution/codec/bitrate read off the pipeline's stream tags
+    stream_info: Arc<Mutex<Option<StreamInfo>>>,
+    /// `webrtcbin` element, present only when built with `PreviewTransport::WebRtc`
+    webrtcbin: Option<gst::Element>,
+    /// Callback for outgoing WebRTC signalling messages, registered via `on_signal()`
+    on_signal: Arc<Mutex<Option<Box<dyn Fn(SignalMessage) + Send + Sync>>>>,
+    /// Callback fired whenever the input-selector switches to/from the
+    /// fallback placeholder, registered via `on_fallback_changed()`
+    on_fallback_changed: Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>>,
+    /// Reconnect/stale-detection policy this pipeline was built with
+    retry: RetryPolicy,
 }
 
 impl VideoPipeline {
-    /// Create a new video pipeline for MJPEG preview
+    /// Create a new video pipeline for camera preview, using the transport
+    /// selected by `config::PREVIEW_TRANSPORT` and default settings
     pub fn new() -> Result<Self, PipelineError> {
+        Self::with_config(VideoPipelineConfig::default())
+    }
+
+    /// Create a new video pipeline with explicit URL, resolution, queue depth
+    /// and retry policy settings
+    pub fn with_config(pipeline_config: VideoPipelineConfig) -> Result<Self, PipelineError> {
         gst::init()?;
 
-        // Build the pipeline
+        match config::PREVIEW_TRANSPORT {
+            config::PreviewTransport::Mjpeg => Self::new_mjpeg(pipeline_config),
+            config::PreviewTransport::WebRtc => Self::new_webrtc(pipeline_config),
+        }
+    }
+
+    /// Build the pipeline for MJPEG preview: `souphttpsrc` -> `multipartdemux` -> `jpegdec`
+    fn new_mjpeg(pipeline_config: VideoPipelineConfig) -> Result<Self, PipelineError> {
         let pipeline = gst::Pipeline::new();
 
         // Source: HTTP stream
         let source = gst::ElementFactory::make("souphttpsrc")
-            .property("location", config::CAMERA_PREVIEW_URL)
+            .property("location", pipeline_config.url.as_str())
             .property("is-live", true)
             .property("do-timestamp", true)
             .build()
@@ -74,36 +251,26 @@ impl VideoPipeline {
             .build()
             .map_err(|_| PipelineError::ElementCreation("videoconvert".into()))?;
 
-        // Queue to decouple the pipeline and prevent buffer drops
-        let queue = gst::ElementFactory::make("queue")
-            .property("max-size-buffers", 3u32)
-            .property("max-size-time", 0u64)
-            .property("max-size-bytes", 0u32)
-            .build()
-            .map_err(|_| PipelineError::ElementCreation("queue".into()))?;
-
-        // GTK4 paintable sink
-        let sink = gst::ElementFactory::make("gtk4paintablesink")
-            .build()
-            .map_err(|_| PipelineError::ElementCreation("gtk4paintablesink".into()))?;
-
-        // Get the paintable from the sink
-        let paintable = sink.property::<gtk::gdk::Paintable>("paintable");
-
-        // Add elements to pipeline
-        pipeline.add_many([&source, &demux, &decoder, &convert, &queue, &sink])?;
+        pipeline.add_many([&source, &demux, &decoder, &convert])?;
 
-        // Link source to demux
+        // Link source to demux, and decoder to convert (demux to decoder is
+        // linked dynamically once the multipart stream connects)
         source.link(&demux)?;
-
-        // Link decoder to convert to queue to sink
         decoder.link(&convert)?;
-        convert.link(&queue)?;
-        queue.link(&sink)?;
 
         // Track frame timing
         let last_frame_time = Arc::new(AtomicU64::new(0));
         let frame_count = Arc::new(AtomicU64::new(0));
+        let is_reconnecting = Arc::new(AtomicBool::new(false));
+        let showing_fallback = Arc::new(AtomicBool::new(false));
+        let num_retry = Arc::new(AtomicU64::new(0));
+        let last_retry_reason = Arc::new(Mutex::new(RetryReason::None));
+        let buffering_percent = Arc::new(AtomicI32::new(100));
+        let stream_info: Arc<Mutex<Option<StreamInfo>>> = Arc::new(Mutex::new(None));
+        let on_signal: Arc<Mutex<Option<Box<dyn Fn(SignalMessage) + Send + Sync>>>> =
+            Arc::new(Mutex::new(None));
+        let on_fallback_changed: Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>> =
+            Arc::new(Mutex::new(None));
 
         // Connect demux pad-added signal to link to decoder
         let decoder_weak = decoder.downgrade();
@@ -132,31 +299,206 @@ impl VideoPipeline {
             }
         });
 
-        // Add probe on decoder src pad to track frames
-        let last_frame_probe = last_frame_time.clone();
-        let frame_count_probe = frame_count.clone();
-        if let Some(src_pad) = decoder.static_pad("src") {
-            src_pad.add_probe(gst::PadProbeType::BUFFER, move |_, _| {
-                let count = frame_count_probe.fetch_add(1, Ordering::SeqCst) + 1;
-                last_frame_probe.store(now_millis(), Ordering::SeqCst);
-
-                // Log periodically
-                if count == 1 {
-                    log::info!("[PIPELINE] First frame decoded!");
-                } else if count % 300 == 0 {
-                    log::debug!("[PIPELINE] Frames decoded: {}", count);
+        let tail = build_shared_tail(
+            &pipeline,
+            &convert,
+            &last_frame_time,
+            &frame_count,
+            &showing_fallback,
+            &on_fallback_changed,
+            pipeline_config.resolution,
+            pipeline_config.queue_max_buffers,
+        )?;
+
+        Ok(Self {
+            pipeline,
+            paintable: tail.paintable,
+            is_reconnecting,
+            last_frame_time,
+            frame_count,
+            input_selector: tail.input_selector,
+            live_pad: tail.live_pad,
+            fallback_pad: tail.fallback_pad,
+            showing_fallback,
+            num_retry,
+            last_retry_reason,
+            buffering_percent,
+            snapshot_sink: tail.snapshot_sink,
+            stream_info,
+            webrtcbin: None,
+            on_signal,
+            on_fallback_changed,
+            retry: pipeline_config.retry,
+        })
+    }
+
+    /// Build the pipeline for low-latency WebRTC preview: `webrtcbin` -> `decodebin`,
+    /// reusing the same fallback/snapshot/reconnect plumbing as the MJPEG path.
+    /// Signalling (SDP offer, ICE candidates) is surfaced via `on_signal()` /
+    /// `set_remote_answer()` / `add_ice_candidate()` rather than handled here, so
+    /// this module stays independent of the WebSocket transport.
+    fn new_webrtc(pipeline_config: VideoPipelineConfig) -> Result<Self, PipelineError> {
+        let pipeline = gst::Pipeline::new();
+
+        let webrtcbin = gst::ElementFactory::make("webrtcbin")
+            .property("stun-server", config::STUN_SERVER)
+            .build()
+            .map_err(|_| PipelineError::ElementCreation("webrtcbin".into()))?;
+
+        let decodebin = gst::ElementFactory::make("decodebin")
+            .build()
+            .map_err(|_| PipelineError::ElementCreation("decodebin".into()))?;
+
+        let convert = gst::ElementFactory::make("videoconvert")
+            .build()
+            .map_err(|_| PipelineError::ElementCreation("videoconvert".into()))?;
+
+        pipeline.add_many([&webrtcbin, &decodebin, &convert])?;
+
+        // Request a recvonly video transceiver so the offer we generate below
+        // actually asks the remote end for a video track
+        let caps = gst::Caps::builder("application/x-rtp")
+            .field("media", "video")
+            .field("encoding-name", "H264")
+            .field("payload", 96i32)
+            .build();
+        webrtcbin.emit_by_name::<gst_webrtc::WebRTCRTPTransceiver>(
+            "add-transceiver",
+            &[&gst_webrtc::WebRTCRTPTransceiverDirection::Recvonly, &caps],
+        );
+
+        let last_frame_time = Arc::new(AtomicU64::new(0));
+        let frame_count = Arc::new(AtomicU64::new(0));
+        let is_reconnecting = Arc::new(AtomicBool::new(false));
+        let showing_fallback = Arc::new(AtomicBool::new(false));
+        let num_retry = Arc::new(AtomicU64::new(0));
+        let last_retry_reason = Arc::new(Mutex::new(RetryReason::None));
+        let buffering_percent = Arc::new(AtomicI32::new(100));
+        let stream_info: Arc<Mutex<Option<StreamInfo>>> = Arc::new(Mutex::new(None));
+        let on_signal: Arc<Mutex<Option<Box<dyn Fn(SignalMessage) + Send + Sync>>>> =
+            Arc::new(Mutex::new(None));
+        let on_fallback_changed: Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>> =
+            Arc::new(Mutex::new(None));
+
+        // Link the remote video track to decodebin once webrtcbin negotiates it
+        let decodebin_weak = decodebin.downgrade();
+        webrtcbin.connect_pad_added(move |_webrtcbin, src_pad| {
+            if src_pad.direction() != gst::PadDirection::Src {
+                return;
+            }
+            log::info!("[PIPELINE] webrtcbin pad added: {}", src_pad.name());
+            if let Some(decodebin) = decodebin_weak.upgrade() {
+                if let Some(sink_pad) = decodebin.static_pad("sink") {
+                    if !sink_pad.is_linked() {
+                        if let Err(e) = src_pad.link(&sink_pad) {
+                            log::error!("[PIPELINE] Failed to link webrtcbin to decodebin: {:?}", e);
+                        }
+                    }
+                }
+            }
+        });
+
+        let convert_weak = convert.downgrade();
+        decodebin.connect_pad_added(move |_decodebin, src_pad| {
+            if let Some(convert) = convert_weak.upgrade() {
+                if let Some(sink_pad) = convert.static_pad("sink") {
+                    if !sink_pad.is_linked() {
+                        if let Err(e) = src_pad.link(&sink_pad) {
+                            log::error!(
+                                "[PIPELINE] Failed to link decodebin to videoconvert: {:?}",
+                                e
+                            );
+                        } else {
+                            log::info!("[PIPELINE] Linked decodebin to videoconvert successfully");
+                        }
+                    }
                 }
+            }
+        });
+
+        // Generate and surface a local SDP offer whenever negotiation is needed
+        let on_signal_negotiation = on_signal.clone();
+        webrtcbin.connect("on-negotiation-needed", false, move |values| {
+            let webrtcbin = values[0].get::<gst::Element>().ok()?;
+            let on_signal = on_signal_negotiation.clone();
+            let webrtcbin_reply = webrtcbin.clone();
+
+            let promise = gst::Promise::with_change_func(move |reply| {
+                let reply = match reply {
+                    Ok(Some(reply)) => reply,
+                    _ => {
+                        log::error!("[PIPELINE] create-offer request failed");
+                        return;
+                    }
+                };
+                let offer = match reply
+                    .value("offer")
+                    .and_then(|o| o.get::<gst_webrtc::WebRTCSessionDescription>())
+                {
+                    Ok(offer) => offer,
+                    Err(_) => {
+                        log::error!("[PIPELINE] create-offer reply missing offer");
+                        return;
+                    }
+                };
 
-                gst::PadProbeReturn::Ok
+                webrtcbin_reply
+                    .emit_by_name::<()>("set-local-description", &[&offer, &None::<gst::Promise>]);
+
+                if let Some(callback) = on_signal.lock().unwrap().as_ref() {
+                    callback(SignalMessage::Offer(offer.sdp().as_text().unwrap_or_default()));
+                }
             });
-        }
+
+            webrtcbin.emit_by_name::<()>("create-offer", &[&None::<gst::Structure>, &promise]);
+            None
+        });
+
+        // Forward locally-gathered ICE candidates to the signalling channel
+        let on_signal_ice = on_signal.clone();
+        webrtcbin.connect("on-ice-candidate", false, move |values| {
+            let sdp_mline_index = values[1].get::<u32>().ok()?;
+            let candidate = values[2].get::<String>().ok()?;
+
+            if let Some(callback) = on_signal_ice.lock().unwrap().as_ref() {
+                callback(SignalMessage::IceCandidate {
+                    candidate,
+                    sdp_mline_index,
+                });
+            }
+            None
+        });
+
+        let tail = build_shared_tail(
+            &pipeline,
+            &convert,
+            &last_frame_time,
+            &frame_count,
+            &showing_fallback,
+            &on_fallback_changed,
+            pipeline_config.resolution,
+            pipeline_config.queue_max_buffers,
+        )?;
 
         Ok(Self {
             pipeline,
-            paintable,
-            is_reconnecting: Arc::new(AtomicBool::new(false)),
+            paintable: tail.paintable,
+            is_reconnecting,
             last_frame_time,
             frame_count,
+            input_selector: tail.input_selector,
+            live_pad: tail.live_pad,
+            fallback_pad: tail.fallback_pad,
+            showing_fallback,
+            num_retry,
+            last_retry_reason,
+            buffering_percent,
+            snapshot_sink: tail.snapshot_sink,
+            stream_info,
+            webrtcbin: Some(webrtcbin),
+            on_signal,
+            on_fallback_changed,
+            retry: pipeline_config.retry,
         })
     }
 
@@ -165,6 +507,91 @@ impl VideoPipeline {
         &self.paintable
     }
 
+    /// Register a callback for outgoing WebRTC signalling messages (SDP offer /
+    /// ICE candidates), typically forwarding them over the kiosk `WsHandle`.
+    /// No-op when the pipeline was built with `PreviewTransport::Mjpeg`.
+    pub fn on_signal<F>(&self, callback: F)
+    where
+        F: Fn(SignalMessage) + Send + Sync + 'static,
+    {
+        *self.on_signal.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Register a callback fired whenever the input-selector switches to the
+    /// fallback placeholder (`true`) or back to the live feed (`false`), so
+    /// the UI/state machine can block capture and show a "reconnecting…"
+    /// overlay while the camera is unavailable.
+    pub fn on_fallback_changed<F>(&self, callback: F)
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        *self.on_fallback_changed.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Apply a remote SDP answer received over the signalling channel.
+    /// No-op when the pipeline was built with `PreviewTransport::Mjpeg`.
+    pub fn set_remote_answer(&self, sdp: &str) -> Result<(), PipelineError> {
+        let Some(webrtcbin) = &self.webrtcbin else {
+            return Ok(());
+        };
+
+        let sdp_message = gst_sdp::SDPMessage::parse_buffer(sdp.as_bytes())
+            .map_err(|_| PipelineError::ElementCreation("SDP answer".into()))?;
+        let answer =
+            gst_webrtc::WebRTCSessionDescription::new(gst_webrtc::WebRTCSDPType::Answer, sdp_message);
+        webrtcbin.emit_by_name::<()>("set-remote-description", &[&answer, &None::<gst::Promise>]);
+        Ok(())
+    }
+
+    /// Feed a remote ICE candidate received over the signalling channel into `webrtcbin`.
+    /// No-op when the pipeline was built with `PreviewTransport::Mjpeg`.
+    pub fn add_ice_candidate(&self, sdp_mline_index: u32, candidate: &str) {
+        if let Some(webrtcbin) = &self.webrtcbin {
+            webrtcbin.emit_by_name::<()>("add-ice-candidate", &[&sdp_mline_index, &candidate]);
+        }
+    }
+
+    /// Grab a full-resolution JPEG still frame from the live preview pipeline,
+    /// without a round-trip to the backend.
+    pub fn capture_still(&self) -> Result<Vec<u8>, PipelineError> {
+        let sample = self
+            .snapshot_sink
+            .try_pull_sample(gst::ClockTime::from_mseconds(200))
+            .ok_or(PipelineError::NoSnapshot)?;
+
+        let buffer = sample.buffer().ok_or(PipelineError::NoSnapshot)?;
+        let map = buffer
+            .map_readable()
+            .map_err(|_| PipelineError::NoSnapshot)?;
+
+        Ok(map.as_slice().to_vec())
+    }
+
+    /// Snapshot of current pipeline health, for a connection-quality indicator
+    pub fn stats(&self) -> PipelineStats {
+        let last_frame = self.last_frame_time.load(Ordering::SeqCst);
+        let ms_since_last_frame = if last_frame == 0 {
+            0
+        } else {
+            now_millis().saturating_sub(last_frame)
+        };
+
+        PipelineStats {
+            num_retry: self.num_retry.load(Ordering::SeqCst),
+            last_retry_reason: *self.last_retry_reason.lock().unwrap(),
+            buffering_percent: self.buffering_percent.load(Ordering::SeqCst),
+            total_frames: self.frame_count.load(Ordering::SeqCst),
+            ms_since_last_frame,
+        }
+    }
+
+    /// Latest resolution/codec/bitrate read off the pipeline's stream tags,
+    /// for a debug overlay confirming the camera feed is healthy.
+    /// `None` until the pipeline has negotiated caps and received tags.
+    pub fn stream_info(&self) -> Option<StreamInfo> {
+        self.stream_info.lock().unwrap().clone()
+    }
+
     /// Start the pipeline
     pub fn play(&self) -> Result<(), PipelineError> {
         log::info!("Starting video pipeline");
@@ -189,9 +616,28 @@ impl VideoPipeline {
         let is_reconnecting = self.is_reconnecting.clone();
         let last_frame_time = self.last_frame_time.clone();
         let frame_count = self.frame_count.clone();
+        let input_selector = self.input_selector.clone();
+        let fallback_pad = self.fallback_pad.clone();
+        let showing_fallback = self.showing_fallback.clone();
+        let on_fallback_changed = self.on_fallback_changed.clone();
+        let num_retry = self.num_retry.clone();
+        let last_retry_reason = self.last_retry_reason.clone();
+        let buffering_percent = self.buffering_percent.clone();
+        let stream_info = self.stream_info.clone();
+        let snapshot_sink = self.snapshot_sink.clone();
+        let retry = self.retry;
 
         if let Some(bus) = self.pipeline.bus() {
             let is_reconnecting_bus = is_reconnecting.clone();
+            let input_selector_bus = input_selector.clone();
+            let fallback_pad_bus = fallback_pad.clone();
+            let showing_fallback_bus = showing_fallback.clone();
+            let on_fallback_changed_bus = on_fallback_changed.clone();
+            let num_retry_bus = num_retry.clone();
+            let last_retry_reason_bus = last_retry_reason.clone();
+            let buffering_percent_bus = buffering_percent.clone();
+            let stream_info_bus = stream_info.clone();
+            let snapshot_sink_bus = snapshot_sink.clone();
             let _ = bus.add_watch_local(move |_bus, msg| {
                 use gstreamer::MessageView;
 
@@ -207,13 +653,27 @@ impl VideoPipeline {
                             err.error(),
                             err.debug()
                         );
-                        schedule_reconnect(&pipeline_weak, &is_reconnecting_bus);
+                        activate_fallback(
+                            &input_selector_bus,
+                            &fallback_pad_bus,
+                            &showing_fallback_bus,
+                            &on_fallback_changed_bus,
+                        );
+                        record_retry(&num_retry_bus, &last_retry_reason_bus, RetryReason::Error);
+                        schedule_reconnect(&pipeline_weak, &is_reconnecting_bus, retry);
                     }
                     MessageView::Eos(_) => {
                         log::warn!(
                             "[PIPELINE] End of stream - camera disconnected or stream ended"
                         );
-                        schedule_reconnect(&pipeline_weak, &is_reconnecting_bus);
+                        activate_fallback(
+                            &input_selector_bus,
+                            &fallback_pad_bus,
+                            &showing_fallback_bus,
+                            &on_fallback_changed_bus,
+                        );
+                        record_retry(&num_retry_bus, &last_retry_reason_bus, RetryReason::Eos);
+                        schedule_reconnect(&pipeline_weak, &is_reconnecting_bus, retry);
                     }
                     MessageView::Warning(warn) => {
                         let src_name = msg
@@ -241,10 +701,14 @@ impl VideoPipeline {
                     }
                     MessageView::Buffering(buffering) => {
                         log::debug!("[PIPELINE] Buffering: {}%", buffering.percent());
+                        buffering_percent_bus.store(buffering.percent(), Ordering::SeqCst);
                     }
                     MessageView::Latency(_) => {
                         log::debug!("[PIPELINE] Latency update");
                     }
+                    MessageView::Tag(tag) => {
+                        update_stream_info(tag.tag(), &snapshot_sink_bus, &stream_info_bus);
+                    }
                     _ => {}
                 }
                 glib::ControlFlow::Continue
@@ -252,19 +716,38 @@ impl VideoPipeline {
         }
 
         // Set up stale frame detection
-        self.setup_stale_frame_detection(is_reconnecting, last_frame_time, frame_count);
+        self.setup_stale_frame_detection(
+            is_reconnecting,
+            last_frame_time,
+            frame_count,
+            input_selector,
+            fallback_pad,
+            showing_fallback,
+            on_fallback_changed,
+            num_retry,
+            last_retry_reason,
+            retry,
+        );
     }
 
     /// Periodically check if frames are still coming in
+    #[allow(clippy::too_many_arguments)]
     fn setup_stale_frame_detection(
         &self,
         is_reconnecting: Arc<AtomicBool>,
         last_frame_time: Arc<AtomicU64>,
         frame_count: Arc<AtomicU64>,
+        input_selector: gst::Element,
+        fallback_pad: gst::Pad,
+        showing_fallback: Arc<AtomicBool>,
+        on_fallback_changed: Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>>,
+        num_retry: Arc<AtomicU64>,
+        last_retry_reason: Arc<Mutex<RetryReason>>,
+        retry: RetryPolicy,
     ) {
         let pipeline_weak = self.pipeline.downgrade();
 
-        glib::timeout_add_local(Duration::from_millis(STALE_CHECK_INTERVAL_MS), move || {
+        glib::timeout_add_local(Duration::from_millis(retry.stale_check_interval_ms), move || {
             // Don't check if we're already reconnecting
             if is_reconnecting.load(Ordering::SeqCst) {
                 return glib::ControlFlow::Continue;
@@ -278,17 +761,25 @@ impl VideoPipeline {
             if last_frame > 0 {
                 let elapsed = now.saturating_sub(last_frame);
 
-                if elapsed > STALE_THRESHOLD_MS {
+                if elapsed > retry.stale_threshold_ms {
                     log::warn!(
                         "[PIPELINE] Stream appears stale! No frames for {}ms (total frames: {})",
                         elapsed,
                         frames
                     );
 
+                    activate_fallback(
+                        &input_selector,
+                        &fallback_pad,
+                        &showing_fallback,
+                        &on_fallback_changed,
+                    );
+                    record_retry(&num_retry, &last_retry_reason, RetryReason::Stale);
+
                     // Trigger reconnect
                     if let Some(pipeline) = pipeline_weak.upgrade() {
                         log::info!("[PIPELINE] Forcing reconnect due to stale stream");
-                        schedule_reconnect_pipeline(pipeline, is_reconnecting.clone());
+                        schedule_reconnect_pipeline(pipeline, is_reconnecting.clone(), retry);
                     }
                 }
             } else if frames == 0 {
@@ -306,6 +797,208 @@ impl VideoPipeline {
     }
 }
 
+/// Downstream plumbing built by every preview transport
+struct SharedTail {
+    paintable: gtk::gdk::Paintable,
+    input_selector: gst::Element,
+    live_pad: gst::Pad,
+    fallback_pad: gst::Pad,
+    snapshot_sink: gst_app::AppSink,
+}
+
+/// Build the portion of the pipeline shared by every preview transport: the
+/// live/fallback input-selector, the on-demand JPEG snapshot branch, and the
+/// GTK4 paintable sink. `source_convert` must already be added to `pipeline`
+/// and produce raw decoded video frames for whichever transport is active.
+#[allow(clippy::too_many_arguments)]
+fn build_shared_tail(
+    pipeline: &gst::Pipeline,
+    source_convert: &gst::Element,
+    last_frame_time: &Arc<AtomicU64>,
+    frame_count: &Arc<AtomicU64>,
+    showing_fallback: &Arc<AtomicBool>,
+    on_fallback_changed: &Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>>,
+    resolution: VideoResolution,
+    queue_max_buffers: u32,
+) -> Result<SharedTail, PipelineError> {
+    // Scale the preview branch to the configured preview resolution after the
+    // tee, so the snapshot branch keeps the full decoded resolution
+    let scale = gst::ElementFactory::make("videoscale")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("videoscale".into()))?;
+
+    let scale_caps = gst::ElementFactory::make("capsfilter")
+        .property(
+            "caps",
+            gst::Caps::builder("video/x-raw")
+                .field("height", resolution.height())
+                .build(),
+        )
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("capsfilter".into()))?;
+
+    // Queue to decouple the pipeline and prevent buffer drops
+    let queue = gst::ElementFactory::make("queue")
+        .property("max-size-buffers", queue_max_buffers)
+        .property("max-size-time", 0u64)
+        .property("max-size-bytes", 0u32)
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("queue".into()))?;
+
+    // GTK4 paintable sink
+    let sink = gst::ElementFactory::make("gtk4paintablesink")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("gtk4paintablesink".into()))?;
+
+    // Get the paintable from the sink
+    let paintable = sink.property::<gtk::gdk::Paintable>("paintable");
+
+    // Fallback branch: a looping test pattern shown while the live feed is down,
+    // so the kiosk never shows a frozen/black frame during reconnects.
+    let fallback_source = gst::ElementFactory::make("videotestsrc")
+        .property("is-live", true)
+        .property_from_str("pattern", FALLBACK_PATTERN)
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("videotestsrc".into()))?;
+
+    let fallback_convert = gst::ElementFactory::make("videoconvert")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("videoconvert".into()))?;
+
+    // input-selector picks between the live branch and the fallback branch
+    let input_selector = gst::ElementFactory::make("input-selector")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("input-selector".into()))?;
+
+    // Tee off the decoded live video so a still frame can be grabbed on demand
+    // without disturbing the preview branch
+    let tee = gst::ElementFactory::make("tee")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("tee".into()))?;
+
+    let preview_queue = gst::ElementFactory::make("queue")
+        .property("max-size-buffers", queue_max_buffers)
+        .property("max-size-time", 0u64)
+        .property("max-size-bytes", 0u32)
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("queue".into()))?;
+
+    // Snapshot branch: re-encode a single frame to JPEG on request
+    let snapshot_queue = gst::ElementFactory::make("queue")
+        .property_from_str("leaky", "downstream")
+        .property("max-size-buffers", 1u32)
+        .property("max-size-time", 0u64)
+        .property("max-size-bytes", 0u32)
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("queue".into()))?;
+
+    let snapshot_encoder = gst::ElementFactory::make("jpegenc")
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("jpegenc".into()))?;
+
+    let snapshot_sink = gst::ElementFactory::make("appsink")
+        .property("emit-signals", false)
+        .property("max-buffers", 1u32)
+        .property("drop", true)
+        .build()
+        .map_err(|_| PipelineError::ElementCreation("appsink".into()))?;
+    let snapshot_sink = snapshot_sink
+        .downcast::<gst_app::AppSink>()
+        .map_err(|_| PipelineError::ElementCreation("appsink".into()))?;
+
+    // Add elements to pipeline
+    pipeline.add_many([
+        &scale,
+        &scale_caps,
+        &fallback_source,
+        &fallback_convert,
+        &input_selector,
+        &tee,
+        &preview_queue,
+        &snapshot_queue,
+        &snapshot_encoder,
+        &queue,
+        &sink,
+    ])?;
+    pipeline.add(snapshot_sink.upcast_ref::<gst::Element>())?;
+
+    // Tee the decoded frames into the preview branch (scaled to the configured
+    // resolution) and the full-resolution on-demand snapshot branch
+    source_convert.link(&tee)?;
+    tee.link(&scale)?;
+    scale.link(&scale_caps)?;
+    scale_caps.link(&preview_queue)?;
+    tee.link(&snapshot_queue)?;
+    snapshot_queue.link(&snapshot_encoder)?;
+    snapshot_encoder.link(snapshot_sink.upcast_ref::<gst::Element>())?;
+
+    let live_pad = input_selector
+        .request_pad_simple("sink_%u")
+        .ok_or_else(|| PipelineError::ElementCreation("input-selector sink pad".into()))?;
+    let preview_queue_src = preview_queue
+        .static_pad("src")
+        .ok_or_else(|| PipelineError::ElementCreation("queue src pad".into()))?;
+    preview_queue_src.link(&live_pad)?;
+
+    // Link the fallback branch to its own input-selector pad
+    fallback_source.link(&fallback_convert)?;
+    let fallback_pad = input_selector
+        .request_pad_simple("sink_%u")
+        .ok_or_else(|| PipelineError::ElementCreation("input-selector sink pad".into()))?;
+    let fallback_convert_src = fallback_convert
+        .static_pad("src")
+        .ok_or_else(|| PipelineError::ElementCreation("videoconvert src pad".into()))?;
+    fallback_convert_src.link(&fallback_pad)?;
+
+    // Start on the live branch
+    input_selector.set_property("active-pad", &live_pad);
+
+    input_selector.link(&queue)?;
+    queue.link(&sink)?;
+
+    // Add probe on the tee's sink pad to track frames, and flip the input-selector
+    // back to the live branch as soon as a fresh frame arrives - regardless of
+    // which transport is feeding it
+    let last_frame_probe = last_frame_time.clone();
+    let frame_count_probe = frame_count.clone();
+    let showing_fallback_probe = showing_fallback.clone();
+    let on_fallback_changed_probe = on_fallback_changed.clone();
+    let input_selector_probe = input_selector.clone();
+    let live_pad_probe = live_pad.clone();
+    if let Some(sink_pad) = tee.static_pad("sink") {
+        sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_, _| {
+            let count = frame_count_probe.fetch_add(1, Ordering::SeqCst) + 1;
+            last_frame_probe.store(now_millis(), Ordering::SeqCst);
+
+            // Log periodically
+            if count == 1 {
+                log::info!("[PIPELINE] First frame decoded!");
+            } else if count % 300 == 0 {
+                log::debug!("[PIPELINE] Frames decoded: {}", count);
+            }
+
+            // Live frames are flowing again - drop the placeholder
+            if showing_fallback_probe.swap(false, Ordering::SeqCst) {
+                log::info!("[PIPELINE] Live frame received, switching off placeholder");
+                input_selector_probe.set_property("active-pad", &live_pad_probe);
+                if let Some(callback) = on_fallback_changed_probe.lock().unwrap().as_ref() {
+                    callback(false);
+                }
+            }
+
+            gst::PadProbeReturn::Ok
+        });
+    }
+
+    Ok(SharedTail {
+        paintable,
+        input_selector,
+        live_pad,
+        fallback_pad,
+        snapshot_sink,
+    })
+}
+
 /// Get current time in milliseconds
 fn now_millis() -> u64 {
     std::time::SystemTime::now()
@@ -314,25 +1007,107 @@ fn now_millis() -> u64 {
         .as_millis() as u64
 }
 
+/// Switch the input-selector to the "reconnecting…" placeholder branch, if not already showing it
+fn activate_fallback(
+    input_selector: &gst::Element,
+    fallback_pad: &gst::Pad,
+    showing_fallback: &Arc<AtomicBool>,
+    on_fallback_changed: &Arc<Mutex<Option<Box<dyn Fn(bool) + Send + Sync>>>>,
+) {
+    if !showing_fallback.swap(true, Ordering::SeqCst) {
+        log::info!("[PIPELINE] Switching to fallback placeholder");
+        input_selector.set_property("active-pad", fallback_pad);
+        if let Some(callback) = on_fallback_changed.lock().unwrap().as_ref() {
+            callback(true);
+        }
+    }
+}
+
+/// Record a reconnect attempt for `VideoPipeline::stats()`
+fn record_retry(
+    num_retry: &Arc<AtomicU64>,
+    last_retry_reason: &Arc<Mutex<RetryReason>>,
+    reason: RetryReason,
+) {
+    num_retry.fetch_add(1, Ordering::SeqCst);
+    *last_retry_reason.lock().unwrap() = reason;
+}
+
+/// Merge a stream tag list into `VideoPipeline::stream_info()`, pulling the
+/// negotiated codec/bitrate off the tags and the resolution off the snapshot
+/// sink's current caps (per the GStreamer playbin tutorial's `n-video`/tag walk)
+fn update_stream_info(
+    tags: &gst::TagList,
+    snapshot_sink: &gst_app::AppSink,
+    stream_info: &Arc<Mutex<Option<StreamInfo>>>,
+) {
+    let codec = tags.get::<gst::tags::VideoCodec>().map(|v| v.get().to_string());
+    let bitrate = tags.get::<gst::tags::Bitrate>().map(|v| v.get());
+
+    if codec.is_none() && bitrate.is_none() {
+        return;
+    }
+
+    let dimensions = snapshot_sink
+        .static_pad("sink")
+        .and_then(|pad| pad.current_caps())
+        .and_then(|caps| caps.structure(0).map(|s| s.to_owned()))
+        .and_then(|s| s.get::<i32>("width").ok().zip(s.get::<i32>("height").ok()));
+
+    let mut info = stream_info.lock().unwrap();
+    let previous = info.clone().unwrap_or_default();
+    *info = Some(StreamInfo {
+        width: dimensions.map(|(w, _)| w).unwrap_or(previous.width),
+        height: dimensions.map(|(_, h)| h).unwrap_or(previous.height),
+        codec: codec.unwrap_or(previous.codec),
+        bitrate: bitrate.unwrap_or(previous.bitrate),
+    });
+}
+
+/// Exponential backoff with jitter: `min(base * 2^attempt, cap)` plus up to 20%
+/// extra, so a still-booting camera isn't hammered at a fixed interval
+fn backoff_delay_ms(retry: &RetryPolicy, attempt: u32) -> u64 {
+    let exp = retry.base_delay_ms.saturating_mul(1u64 << attempt.min(20));
+    let capped = exp.min(retry.max_delay_ms);
+    capped.saturating_add((capped as f64 * 0.2 * jitter_fraction()) as u64)
+}
+
+/// A cheap, non-cryptographic jitter source in `[0.0, 1.0)` derived from the
+/// clock, avoiding a dependency on a random number generator crate for a
+/// one-off backoff nudge
+fn jitter_fraction() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    (nanos % 1000) as f64 / 1000.0
+}
+
 /// Schedule a reconnection attempt by restarting the pipeline
 fn schedule_reconnect(
     pipeline_weak: &glib::WeakRef<gst::Pipeline>,
     is_reconnecting: &Arc<AtomicBool>,
+    retry: RetryPolicy,
 ) {
     if let Some(pipeline) = pipeline_weak.upgrade() {
-        schedule_reconnect_pipeline(pipeline, is_reconnecting.clone());
+        schedule_reconnect_pipeline(pipeline, is_reconnecting.clone(), retry);
     }
 }
 
 /// Schedule reconnection for a pipeline (used by both error handler and stale detection)
-fn schedule_reconnect_pipeline(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>) {
+fn schedule_reconnect_pipeline(
+    pipeline: gst::Pipeline,
+    is_reconnecting: Arc<AtomicBool>,
+    retry: RetryPolicy,
+) {
     if !is_reconnecting.swap(true, Ordering::SeqCst) {
         let (_, current_state, _) = pipeline.state(gst::ClockTime::from_mseconds(10));
+        let delay = backoff_delay_ms(&retry, 0);
 
         log::info!(
             "[PIPELINE] Initiating reconnect (current state: {:?}), will retry in {}ms",
             current_state,
-            RECONNECT_DELAY_MS
+            delay
         );
 
         // Stop pipeline
@@ -343,22 +1118,25 @@ fn schedule_reconnect_pipeline(pipeline: gst::Pipeline, is_reconnecting: Arc<Ato
         }
 
         // Schedule restart
-        schedule_restart(pipeline, is_reconnecting, 0);
+        schedule_restart(pipeline, is_reconnecting, retry, 0);
     } else {
         log::debug!("[PIPELINE] Reconnect already in progress, skipping");
     }
 }
 
-/// Maximum number of restart attempts before giving up
-const MAX_RESTART_ATTEMPTS: u32 = 10;
-
 /// Try to restart the pipeline, retrying if it fails
-fn schedule_restart(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>, attempt: u32) {
-    glib::timeout_add_local_once(Duration::from_millis(RECONNECT_DELAY_MS), move || {
+fn schedule_restart(
+    pipeline: gst::Pipeline,
+    is_reconnecting: Arc<AtomicBool>,
+    retry: RetryPolicy,
+    attempt: u32,
+) {
+    let delay = backoff_delay_ms(&retry, attempt);
+    glib::timeout_add_local_once(Duration::from_millis(delay), move || {
         log::info!(
             "[PIPELINE] Reconnection attempt {} of {}",
             attempt + 1,
-            MAX_RESTART_ATTEMPTS
+            retry.max_attempts
         );
 
         // First set to NULL to fully reset
@@ -375,19 +1153,19 @@ fn schedule_restart(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>, a
             match pipeline_clone.set_state(gst::State::Playing) {
                 Ok(gst::StateChangeSuccess::Success) => {
                     log::info!("[PIPELINE] State change to PLAYING succeeded immediately");
-                    verify_reconnection(pipeline_clone, is_reconnecting_clone, attempt);
+                    verify_reconnection(pipeline_clone, is_reconnecting_clone, retry, attempt);
                 }
                 Ok(gst::StateChangeSuccess::Async) => {
                     log::info!("[PIPELINE] State change to PLAYING is async, waiting...");
-                    verify_reconnection(pipeline_clone, is_reconnecting_clone, attempt);
+                    verify_reconnection(pipeline_clone, is_reconnecting_clone, retry, attempt);
                 }
                 Ok(gst::StateChangeSuccess::NoPreroll) => {
                     log::info!("[PIPELINE] State change succeeded (no preroll - live source)");
-                    verify_reconnection(pipeline_clone, is_reconnecting_clone, attempt);
+                    verify_reconnection(pipeline_clone, is_reconnecting_clone, retry, attempt);
                 }
                 Err(e) => {
                     log::error!("[PIPELINE] Failed to set PLAYING state: {:?}", e);
-                    retry_or_give_up(pipeline_clone, is_reconnecting_clone, attempt);
+                    retry_or_give_up(pipeline_clone, is_reconnecting_clone, retry, attempt);
                 }
             }
         });
@@ -395,7 +1173,12 @@ fn schedule_restart(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>, a
 }
 
 /// Verify that reconnection actually worked by checking state after a delay
-fn verify_reconnection(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>, attempt: u32) {
+fn verify_reconnection(
+    pipeline: gst::Pipeline,
+    is_reconnecting: Arc<AtomicBool>,
+    retry: RetryPolicy,
+    attempt: u32,
+) {
     glib::timeout_add_local_once(Duration::from_millis(2000), move || {
         let (result, current, pending) = pipeline.state(gst::ClockTime::from_mseconds(100));
 
@@ -414,20 +1197,25 @@ fn verify_reconnection(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>
                 "[PIPELINE] Not playing after restart (state: {:?}), will retry",
                 current
             );
-            retry_or_give_up(pipeline, is_reconnecting, attempt);
+            retry_or_give_up(pipeline, is_reconnecting, retry, attempt);
         }
     });
 }
 
 /// Either retry or give up based on attempt count
-fn retry_or_give_up(pipeline: gst::Pipeline, is_reconnecting: Arc<AtomicBool>, attempt: u32) {
-    if attempt < MAX_RESTART_ATTEMPTS {
+fn retry_or_give_up(
+    pipeline: gst::Pipeline,
+    is_reconnecting: Arc<AtomicBool>,
+    retry: RetryPolicy,
+    attempt: u32,
+) {
+    if attempt + 1 < retry.max_attempts {
         log::info!("[PIPELINE] Scheduling retry attempt {}", attempt + 2);
-        schedule_restart(pipeline, is_reconnecting, attempt + 1);
+        schedule_restart(pipeline, is_reconnecting, retry, attempt + 1);
     } else {
         log::error!(
             "[PIPELINE] GIVING UP after {} attempts - camera preview unavailable!",
-            MAX_RESTART_ATTEMPTS
+            retry.max_attempts
         );
         is_reconnecting.store(false, Ordering::SeqCst);
     }
@@ -0,0 +1,269 @@
+//! GStreamer pipeline for camera-based QR code scanning.
+//!
+//! Unlike `pipeline::VideoPipeline` (which pulls the live preview as MJPEG
+//! from the backend), this reads the kiosk's own camera directly -
+//! `pipewiresrc` under Wayland/PipeWire, falling back to `v4l2src` - since
+//! scanning is a local pairing/setup action with nothing to proxy through
+//! the backend. Modeled on Fractal's `qr_code_scanner`: a `gdk::Paintable`
+//! feeds a live preview while every sample is also decoded on an `appsink`
+//! branch via the `rqrr` crate.
+
+use std::sync::{Arc, Mutex};
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use gtk4 as gtk;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ScannerError {
+    #[error("GStreamer error: {0}")]
+    Gstreamer(#[from] glib::Error),
+    #[error("GStreamer bool error: {0}")]
+    GstreamerBool(#[from] glib::BoolError),
+    #[error("Failed to create element: {0}")]
+    ElementCreation(String),
+    #[error("No usable camera source (tried pipewiresrc and v4l2src)")]
+    NoCameraSource,
+    #[error("State change failed")]
+    StateChange,
+}
+
+/// Minimal grayscale frame view handed to `rqrr::PreparedImage::prepare`,
+/// copying the mapped `appsink` buffer as-is rather than pulling in an
+/// image-decoding crate just to satisfy `rqrr::Image`. Indexes by `stride`
+/// rather than `width`, since GStreamer pads each row to its negotiated
+/// stride and most real camera resolutions aren't stride-aligned.
+struct GrayFrame {
+    width: usize,
+    height: usize,
+    stride: usize,
+    data: Vec<u8>,
+}
+
+impl rqrr::Image for GrayFrame {
+    fn dimensions(&self) -> (u32, u32) {
+        (self.width as u32, self.height as u32)
+    }
+
+    fn at(&self, x: usize, y: usize) -> u8 {
+        self.data[y * self.stride + x]
+    }
+}
+
+/// GStreamer pipeline reading the kiosk's camera for QR scanning: a live
+/// preview branch (for the `ScannerPanel`'s `gtk::Picture`) and a grayscale
+/// `appsink` branch that decodes every sample with `rqrr`.
+pub struct ScannerPipeline {
+    pipeline: gst::Pipeline,
+    paintable: gtk::gdk::Paintable,
+    on_decode: Arc<Mutex<Option<Box<dyn Fn(String) + Send + Sync>>>>,
+}
+
+impl ScannerPipeline {
+    /// Build the pipeline: `pipewiresrc`/`v4l2src` -> `videoconvert` -> `tee`,
+    /// splitting into a `gtk4paintablesink` preview branch and a GRAY8
+    /// `appsink` decode branch.
+    pub fn new() -> Result<Self, ScannerError> {
+        gst::init()?;
+
+        let pipeline = gst::Pipeline::new();
+
+        let source = make_camera_source()?;
+
+        let convert = gst::ElementFactory::make("videoconvert")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("videoconvert".into()))?;
+
+        let tee = gst::ElementFactory::make("tee")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("tee".into()))?;
+
+        // Preview branch
+        let preview_queue = gst::ElementFactory::make("queue")
+            .property("max-size-buffers", 1u32)
+            .property_from_str("leaky", "downstream")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("queue".into()))?;
+
+        let preview_sink = gst::ElementFactory::make("gtk4paintablesink")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("gtk4paintablesink".into()))?;
+
+        // Decode branch - grayscale, no scaling, so modules stay crisp for rqrr
+        let decode_queue = gst::ElementFactory::make("queue")
+            .property("max-size-buffers", 1u32)
+            .property_from_str("leaky", "downstream")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("queue".into()))?;
+
+        let decode_convert = gst::ElementFactory::make("videoconvert")
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("videoconvert".into()))?;
+
+        let decode_caps = gst::ElementFactory::make("capsfilter")
+            .property(
+                "caps",
+                gst::Caps::builder("video/x-raw")
+                    .field("format", "GRAY8")
+                    .build(),
+            )
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("capsfilter".into()))?;
+
+        let decode_sink = gst::ElementFactory::make("appsink")
+            .property("sync", false)
+            .build()
+            .map_err(|_| ScannerError::ElementCreation("appsink".into()))?;
+        let decode_sink: gst_app::AppSink = decode_sink
+            .dynamic_cast()
+            .map_err(|_| ScannerError::ElementCreation("appsink".into()))?;
+
+        pipeline.add_many([
+            &source,
+            &convert,
+            &tee,
+            &preview_queue,
+            &preview_sink,
+            &decode_queue,
+            &decode_convert,
+            &decode_caps,
+            decode_sink.upcast_ref(),
+        ])?;
+
+        source.link(&convert)?;
+        convert.link(&tee)?;
+        preview_queue.link(&preview_sink)?;
+        decode_queue.link(&decode_convert)?;
+        decode_convert.link(&decode_caps)?;
+        decode_caps.link(&decode_sink)?;
+
+        let tee_preview_pad = tee
+            .request_pad_simple("src_%u")
+            .ok_or_else(|| ScannerError::ElementCreation("tee preview src pad".into()))?;
+        let preview_sink_pad = preview_queue
+            .static_pad("sink")
+            .ok_or_else(|| ScannerError::ElementCreation("preview queue sink pad".into()))?;
+        tee_preview_pad.link(&preview_sink_pad)?;
+
+        let tee_decode_pad = tee
+            .request_pad_simple("src_%u")
+            .ok_or_else(|| ScannerError::ElementCreation("tee decode src pad".into()))?;
+        let decode_sink_pad = decode_queue
+            .static_pad("sink")
+            .ok_or_else(|| ScannerError::ElementCreation("decode queue sink pad".into()))?;
+        tee_decode_pad.link(&decode_sink_pad)?;
+
+        let paintable = preview_sink.property::<gtk::gdk::Paintable>("paintable");
+
+        let on_decode: Arc<Mutex<Option<Box<dyn Fn(String) + Send + Sync>>>> =
+            Arc::new(Mutex::new(None));
+
+        let callback_on_decode = on_decode.clone();
+        decode_sink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    match decode_sample(sink) {
+                        Ok(Some(text)) => {
+                            if let Some(cb) = callback_on_decode.lock().unwrap().as_ref() {
+                                cb(text);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => log::error!("Failed to read QR scanner frame: {}", e),
+                    }
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        Ok(Self {
+            pipeline,
+            paintable,
+            on_decode,
+        })
+    }
+
+    /// Paintable driven by the live camera preview, for a `gtk::Picture`
+    pub fn paintable(&self) -> &gtk::gdk::Paintable {
+        &self.paintable
+    }
+
+    /// Register a callback fired on the GStreamer thread with each decoded
+    /// QR payload. Callers must bounce back to the GTK main loop themselves
+    /// (see `ScannerPanel`, which forwards through `AppContext::message_tx`).
+    pub fn on_decode<F>(&self, callback: F)
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        *self.on_decode.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    pub fn play(&self) -> Result<(), ScannerError> {
+        log::info!("Starting QR scanner pipeline");
+        self.pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|_| ScannerError::StateChange)?;
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<(), ScannerError> {
+        log::info!("Stopping QR scanner pipeline");
+        self.pipeline
+            .set_state(gst::State::Null)
+            .map_err(|_| ScannerError::StateChange)?;
+        Ok(())
+    }
+}
+
+impl Drop for ScannerPipeline {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+/// Try `pipewiresrc` first (the native source under the Wayland/PipeWire
+/// stack this kiosk otherwise targets), falling back to `v4l2src` for
+/// X11/older setups where PipeWire camera portals aren't in play.
+fn make_camera_source() -> Result<gst::Element, ScannerError> {
+    gst::ElementFactory::make("pipewiresrc")
+        .build()
+        .or_else(|_| gst::ElementFactory::make("v4l2src").build())
+        .map_err(|_| ScannerError::NoCameraSource)
+}
+
+/// Pull the latest sample off the decode `appsink`, grayscale buffer already
+/// guaranteed by its GRAY8 capsfilter, and decode any QR grids `rqrr` finds
+/// in it. Returns the first successfully decoded payload, if any.
+fn decode_sample(sink: &gst_app::AppSink) -> Result<Option<String>, glib::BoolError> {
+    let sample = sink.pull_sample().map_err(|_| glib::bool_error!("no sample"))?;
+    let caps = sample
+        .caps()
+        .ok_or_else(|| glib::bool_error!("sample has no caps"))?;
+    let video_info = gst_video::VideoInfo::from_caps(caps)
+        .map_err(|_| glib::bool_error!("caps missing video info"))?;
+
+    let buffer = sample
+        .buffer()
+        .ok_or_else(|| glib::bool_error!("sample has no buffer"))?;
+    let map = buffer.map_readable()?;
+
+    let frame = GrayFrame {
+        width: video_info.width() as usize,
+        height: video_info.height() as usize,
+        stride: video_info.stride()[0] as usize,
+        data: map.as_slice().to_vec(),
+    };
+
+    let mut prepared = rqrr::PreparedImage::prepare(frame);
+    for grid in prepared.detect_grids() {
+        match grid.decode() {
+            Ok((_meta, content)) => return Ok(Some(content)),
+            Err(e) => log::error!("Failed to decode QR grid: {}", e),
+        }
+    }
+
+    Ok(None)
+}